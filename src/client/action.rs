@@ -0,0 +1,30 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+/// One api action, implemented by its request type.
+///
+/// The request is sent as the json body and the content of `Response` is decoded into
+/// [`Action::Response`], see [`super::TencentCloudBaseClient::execute`].
+pub trait Action: Serialize {
+    type Response: DeserializeOwned;
+
+    /// service name, e.g. `cvm`, also used as host prefix and signing scope.
+    const SERVICE: &'static str;
+    /// api version, e.g. `2017-03-12`.
+    const VERSION: &'static str;
+    /// action name sent as `X-TC-Action`, e.g. `DescribeInstances`.
+    const ACTION: &'static str;
+}
+
+/// `impl_action!(SERVICE, VERSION, "DescribeZones", DescribeZonesRequest => DescribeZoneResponse);`
+macro_rules! impl_action {
+    ($service:expr, $version:expr, $action:literal, $request:ty => $response:ty) => {
+        impl $crate::client::Action for $request {
+            type Response = $response;
+            const SERVICE: &'static str = $service;
+            const VERSION: &'static str = $version;
+            const ACTION: &'static str = $action;
+        }
+    };
+}
+
+pub(crate) use impl_action;
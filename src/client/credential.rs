@@ -0,0 +1,585 @@
+use std::{
+    fmt,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use time::OffsetDateTime;
+use tracing::debug;
+
+use crate::{
+    config::ClientConfig,
+    constant::Region,
+    error::{Result, TencentCloudError},
+};
+
+use super::{handle_response, TencentCloudClient, ACTION_HEADER};
+
+/// Secret id/key pair used to sign requests.
+///
+/// Temporary credentials also carry the `token` sent as `X-TC-Token` and their expiry.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credential {
+    pub secret_id: String,
+    pub secret_key: String,
+    pub token: Option<String>,
+    pub expired_at: Option<OffsetDateTime>,
+}
+
+impl Credential {
+    pub fn new(secret_id: impl Into<String>, secret_key: impl Into<String>) -> Self {
+        Self {
+            secret_id: secret_id.into(),
+            secret_key: secret_key.into(),
+            token: None,
+            expired_at: None,
+        }
+    }
+
+    /// whether the credential expires within `margin` from now, never for long term keys.
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        self.expired_at
+            .is_some_and(|expired_at| expired_at <= OffsetDateTime::now_utc() + margin)
+    }
+}
+
+fn expired_at(unix_timestamp: i64) -> Option<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp(unix_timestamp).ok()
+}
+
+impl fmt::Debug for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credential")
+            .field("secret_id", &self.secret_id)
+            .field("secret_key", &"***")
+            .field("token", &self.token.as_ref().map(|_| "***"))
+            .field("expired_at", &self.expired_at)
+            .finish()
+    }
+}
+
+/// Source of the credential used by `SignatureMiddleware`, asked once per request.
+#[async_trait]
+pub trait CredentialProvider: Send + Sync + fmt::Debug {
+    async fn credential(&self) -> Result<Credential>;
+}
+
+/// Fixed credential, e.g. from [`ClientConfig`].
+#[derive(Debug, Clone)]
+pub struct StaticCredentialProvider(Credential);
+
+impl StaticCredentialProvider {
+    pub fn new(credential: Credential) -> Self {
+        Self(credential)
+    }
+}
+
+impl From<&ClientConfig> for StaticCredentialProvider {
+    fn from(config: &ClientConfig) -> Self {
+        Self(Credential::new(&config.ak, &config.sk))
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for StaticCredentialProvider {
+    async fn credential(&self) -> Result<Credential> {
+        Ok(self.0.clone())
+    }
+}
+
+const ENV_SECRET_ID: &str = "TENCENTCLOUD_SECRET_ID";
+const ENV_SECRET_KEY: &str = "TENCENTCLOUD_SECRET_KEY";
+const ENV_SESSION_TOKEN: &str = "TENCENTCLOUD_SESSION_TOKEN";
+
+/// `TENCENTCLOUD_SECRET_ID` / `TENCENTCLOUD_SECRET_KEY` and optional `TENCENTCLOUD_SESSION_TOKEN`.
+#[derive(Debug, Clone, Default)]
+pub struct EnvCredentialProvider;
+
+#[async_trait]
+impl CredentialProvider for EnvCredentialProvider {
+    async fn credential(&self) -> Result<Credential> {
+        let var = |key| {
+            std::env::var(key)
+                .ok()
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| TencentCloudError::Credential(format!("env {key} not set")))
+        };
+        Ok(Credential {
+            secret_id: var(ENV_SECRET_ID)?,
+            secret_key: var(ENV_SECRET_KEY)?,
+            token: var(ENV_SESSION_TOKEN).ok(),
+            expired_at: None,
+        })
+    }
+}
+
+/// INI profile file shared with tccli, `~/.tencentcloud/credentials` by default.
+///
+/// ```ini
+/// [default]
+/// secret_id = AKIDxxx
+/// secret_key = xxx
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProfileCredentialProvider {
+    path: Option<PathBuf>,
+    profile: String,
+}
+
+impl Default for ProfileCredentialProvider {
+    fn default() -> Self {
+        Self {
+            path: None,
+            profile: "default".to_owned(),
+        }
+    }
+}
+
+impl ProfileCredentialProvider {
+    pub fn new(path: impl Into<PathBuf>, profile: impl Into<String>) -> Self {
+        Self {
+            path: Some(path.into()),
+            profile: profile.into(),
+        }
+    }
+
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        self.path.clone().or_else(|| {
+            std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(|home| {
+                    PathBuf::from(home)
+                        .join(".tencentcloud")
+                        .join("credentials")
+                })
+        })
+    }
+}
+
+fn parse_profile(content: &str, profile: &str) -> Option<Credential> {
+    let mut in_profile = false;
+    let (mut secret_id, mut secret_key, mut token) = (None, None, None);
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_profile = section.trim() == profile;
+            continue;
+        }
+        if !in_profile {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let value = Some(value.trim().to_owned());
+            match key.trim() {
+                "secret_id" => secret_id = value,
+                "secret_key" => secret_key = value,
+                "token" => token = value,
+                _ => {}
+            }
+        }
+    }
+    Some(Credential {
+        secret_id: secret_id?,
+        secret_key: secret_key?,
+        token,
+        expired_at: None,
+    })
+}
+
+#[async_trait]
+impl CredentialProvider for ProfileCredentialProvider {
+    async fn credential(&self) -> Result<Credential> {
+        let path = self
+            .path()
+            .ok_or_else(|| TencentCloudError::Credential("home directory not found".into()))?;
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| TencentCloudError::Credential(format!("read {}: {e}", path.display())))?;
+        parse_profile(&content, &self.profile).ok_or_else(|| {
+            TencentCloudError::Credential(format!(
+                "profile [{}] in {} missing secret_id or secret_key",
+                self.profile,
+                path.display()
+            ))
+        })
+    }
+}
+
+const CVM_ROLE_ENDPOINT: &str =
+    "http://metadata.tencentyun.com/latest/meta-data/cam/security-credentials/";
+
+/// Temporary credential of the CAM role bound to the current CVM instance, read from the
+/// instance metadata service.
+#[derive(Debug, Clone)]
+pub struct CvmRoleCredentialProvider {
+    client: reqwest::Client,
+    role_name: Option<String>,
+}
+
+impl Default for CvmRoleCredentialProvider {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(1))
+                .build()
+                .unwrap_or_default(),
+            role_name: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CvmRoleCredential {
+    tmp_secret_id: String,
+    tmp_secret_key: String,
+    token: String,
+    expired_time: i64,
+}
+
+impl CvmRoleCredentialProvider {
+    /// role name is looked up from the metadata service when not given.
+    pub fn role_name(mut self, role_name: impl Into<String>) -> Self {
+        self.role_name = Some(role_name.into());
+        self
+    }
+
+    async fn get(&self, url: &str) -> Result<String> {
+        let resp = self.client.get(url).send().await?;
+        let status = resp.status();
+        let body = resp.text().await?;
+        match status.is_success() {
+            true => Ok(body),
+            false => Err(TencentCloudError::Credential(format!(
+                "cvm metadata {url} get code {status}, msg {body}"
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for CvmRoleCredentialProvider {
+    async fn credential(&self) -> Result<Credential> {
+        let role_name = match &self.role_name {
+            Some(role_name) => role_name.clone(),
+            None => self.get(CVM_ROLE_ENDPOINT).await?.trim().to_owned(),
+        };
+        let body = self.get(&format!("{CVM_ROLE_ENDPOINT}{role_name}")).await?;
+        let credential: CvmRoleCredential =
+            serde_json::from_str(&body).map_err(|source| TencentCloudError::Decode {
+                source,
+                body: body.clone(),
+            })?;
+        Ok(Credential {
+            secret_id: credential.tmp_secret_id,
+            secret_key: credential.tmp_secret_key,
+            token: Some(credential.token),
+            expired_at: expired_at(credential.expired_time),
+        })
+    }
+}
+
+/// Temporary credential from STS `AssumeRole`, signed with the credential of `client`.
+#[derive(Debug, Clone)]
+pub struct StsAssumeRoleProvider {
+    client: TencentCloudClient,
+    region: Region,
+    role_arn: String,
+    role_session_name: String,
+    duration_seconds: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AssumeRoleResponse {
+    credentials: AssumeRoleCredential,
+    expired_time: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AssumeRoleCredential {
+    token: String,
+    tmp_secret_id: String,
+    tmp_secret_key: String,
+}
+
+impl StsAssumeRoleProvider {
+    pub fn new(
+        client: TencentCloudClient,
+        region: Region,
+        role_arn: impl Into<String>,
+        role_session_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            region,
+            role_arn: role_arn.into(),
+            role_session_name: role_session_name.into(),
+            duration_seconds: 7200,
+        }
+    }
+
+    pub fn duration_seconds(mut self, duration_seconds: u64) -> Self {
+        self.duration_seconds = duration_seconds;
+        self
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for StsAssumeRoleProvider {
+    async fn credential(&self) -> Result<Credential> {
+        let resp = self
+            .client
+            .client
+            .post("sts", "2018-08-13", Some(&self.region))
+            .header(ACTION_HEADER, "AssumeRole")
+            .json(&json!({
+                "RoleArn": self.role_arn,
+                "RoleSessionName": self.role_session_name,
+                "DurationSeconds": self.duration_seconds,
+            }))
+            .send()
+            .await?;
+        let body: AssumeRoleResponse = handle_response(resp).await?;
+        Ok(Credential {
+            secret_id: body.credentials.tmp_secret_id,
+            secret_key: body.credentials.tmp_secret_key,
+            token: Some(body.credentials.token),
+            expired_at: expired_at(body.expired_time),
+        })
+    }
+}
+
+/// Ask each provider in order and use the first credential found.
+#[derive(Debug, Clone)]
+pub struct CredentialChain {
+    providers: Vec<Arc<dyn CredentialProvider>>,
+}
+
+impl Default for CredentialChain {
+    /// env vars, then the profile file, then the CVM role.
+    fn default() -> Self {
+        Self::new(vec![
+            Arc::new(EnvCredentialProvider),
+            Arc::new(ProfileCredentialProvider::default()),
+            Arc::new(CvmRoleCredentialProvider::default()),
+        ])
+    }
+}
+
+impl CredentialChain {
+    pub fn new(providers: Vec<Arc<dyn CredentialProvider>>) -> Self {
+        Self { providers }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for CredentialChain {
+    async fn credential(&self) -> Result<Credential> {
+        let mut errors = Vec::new();
+        for provider in &self.providers {
+            match provider.credential().await {
+                Ok(credential) => return Ok(credential),
+                Err(e) => {
+                    debug!("credential provider {provider:?} failed: {e}");
+                    errors.push(e.to_string());
+                }
+            }
+        }
+        Err(TencentCloudError::Credential(format!(
+            "no credential found in chain: [{}]",
+            errors.join("; ")
+        )))
+    }
+}
+
+/// Cache the credential of `inner` and refresh it `refresh_before` its expiry.
+///
+/// A credential living shorter than twice `refresh_before` is refreshed at half its lifetime
+/// instead, so short lived tokens are still served from the cache.
+///
+/// While a still valid credential is being refreshed, other requests keep using the cached one
+/// instead of waiting for the refresh.
+#[derive(Debug)]
+pub struct CachedCredentialProvider {
+    inner: Arc<dyn CredentialProvider>,
+    refresh_before: Duration,
+    /// cached credential and how long before its expiry to refresh it.
+    cached: RwLock<Option<(Credential, Duration)>>,
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+impl CachedCredentialProvider {
+    pub fn new(inner: impl CredentialProvider + 'static) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            refresh_before: Duration::from_secs(300),
+            cached: RwLock::new(None),
+            refreshing: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// how long before expiry to refresh, default 5 minutes, at most half the lifetime.
+    pub fn refresh_before(mut self, refresh_before: Duration) -> Self {
+        self.refresh_before = refresh_before;
+        self
+    }
+
+    fn cached(&self) -> Option<(Credential, Duration)> {
+        self.cached.read().unwrap().clone()
+    }
+
+    async fn refresh(&self) -> Result<Credential> {
+        let credential = self.inner.credential().await?;
+        let refresh_before = self.refresh_margin(&credential);
+        *self.cached.write().unwrap() = Some((credential.clone(), refresh_before));
+        Ok(credential)
+    }
+
+    /// `refresh_before` clamped to half the remaining lifetime of a fresh `credential`.
+    fn refresh_margin(&self, credential: &Credential) -> Duration {
+        let lifetime = credential
+            .expired_at
+            .and_then(|expired_at| Duration::try_from(expired_at - OffsetDateTime::now_utc()).ok());
+        match lifetime {
+            Some(lifetime) => self.refresh_before.min(lifetime / 2),
+            None => self.refresh_before,
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for CachedCredentialProvider {
+    async fn credential(&self) -> Result<Credential> {
+        let cached = self.cached();
+        match cached {
+            Some((credential, margin)) if !credential.expires_within(margin) => Ok(credential),
+            Some((credential, _)) if !credential.expires_within(Duration::ZERO) => {
+                // still usable, refresh only if nobody else is doing it
+                let Ok(_guard) = self.refreshing.try_lock() else {
+                    return Ok(credential);
+                };
+                match self.refresh().await {
+                    Ok(refreshed) => Ok(refreshed),
+                    Err(e) => {
+                        debug!("refresh credential failed, keep the cached one: {e}");
+                        Ok(credential)
+                    }
+                }
+            }
+            _ => {
+                let _guard = self.refreshing.lock().await;
+                match self.cached() {
+                    Some((credential, margin)) if !credential.expires_within(margin) => {
+                        Ok(credential)
+                    }
+                    _ => self.refresh().await,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct CountingProvider {
+        calls: Arc<AtomicUsize>,
+        lifetime: Duration,
+    }
+
+    #[async_trait]
+    impl CredentialProvider for CountingProvider {
+        async fn credential(&self) -> Result<Credential> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Credential {
+                token: Some(format!("token-{n}")),
+                expired_at: Some(OffsetDateTime::now_utc() + self.lifetime),
+                ..Credential::new("id", "key")
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_credential() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = CachedCredentialProvider::new(CountingProvider {
+            calls: calls.clone(),
+            lifetime: Duration::from_secs(3600),
+        });
+        let first = provider.credential().await.unwrap();
+        let second = provider.credential().await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // living shorter than the refresh window, cached until half its lifetime
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = CachedCredentialProvider::new(CountingProvider {
+            calls: calls.clone(),
+            lifetime: Duration::from_secs(60),
+        });
+        let first = provider.credential().await.unwrap();
+        let second = provider.credential().await.unwrap();
+        assert_eq!(first.token.as_deref(), Some("token-0"));
+        assert_eq!(second, first);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // past half its lifetime, refreshed
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = CachedCredentialProvider::new(CountingProvider {
+            calls: calls.clone(),
+            lifetime: Duration::from_millis(200),
+        });
+        let first = provider.credential().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        let second = provider.credential().await.unwrap();
+        assert_eq!(first.token.as_deref(), Some("token-0"));
+        assert_eq!(second.token.as_deref(), Some("token-1"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        let content = r#"
+# tccli credentials
+[default]
+secret_id = AKIDdefault
+secret_key = default-key
+
+[ci]
+secret_id=AKIDci
+secret_key=ci-key
+token=ci-token
+"#;
+        assert_eq!(
+            parse_profile(content, "default"),
+            Some(Credential::new("AKIDdefault", "default-key"))
+        );
+        assert_eq!(
+            parse_profile(content, "ci"),
+            Some(Credential {
+                secret_id: "AKIDci".into(),
+                secret_key: "ci-key".into(),
+                token: Some("ci-token".into()),
+                expired_at: None,
+            })
+        );
+        assert_eq!(parse_profile(content, "missing"), None);
+    }
+}
@@ -1,130 +1,948 @@
-use reqwest::StatusCode;
-use serde::Deserialize;
-use serde_json::json;
-use tracing::debug;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+use time::OffsetDateTime;
+use uuid::Uuid;
 
 use crate::{
-    client::constant::{ACTION_HEADER, REGION_HEADER},
+    client::{impl_action, impl_paginate, Paginator},
     constant::{InstanceType, Region},
+    error::{Result, TencentCloudError},
+    error_code::ErrorCode,
 };
 
 use super::*;
 
 pub struct CVMInstanceBuilder {
     client: Arc<TencentCloudBaseClient>,
-    service_name: String,
-    version: String,
 }
 
-/// DescribeInstancesResponse
-#[derive(Debug, Deserialize)]
+/// DescribeInstancesRequest
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct DescribeInstancesResponse {
-    pub response: DescribeInstancesResponseInner,
+pub struct DescribeInstancesRequest {
+    /// at most 100, cannot be combined with `filters`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub instance_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// page size, at most 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+impl DescribeInstancesRequest {
+    pub fn instance_ids(
+        mut self,
+        instance_ids: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.instance_ids.extend(ids(instance_ids));
+        self
+    }
+    /// filters of the same kind are OR-ed, e.g. two zones, different kinds are AND-ed.
+    pub fn filter(mut self, filter: InstanceFilter) -> Self {
+        let (name, value) = filter.into_pair();
+        push_filter(&mut self.filters, name, value);
+        self
+    }
+}
+
+/// documented `Filters` of DescribeInstances
+#[derive(Debug, Clone)]
+pub enum InstanceFilter {
+    Zone(String),
+    InstanceState(InstanceState),
+    InstanceName(String),
+    InstanceChargeType(InstanceChargeType),
+    PrivateIpAddress(String),
+    PublicIpAddress(String),
+    InstanceId(String),
+    ProjectId(i64),
+    /// instances having the tag key
+    TagKey(String),
+    TagValue(String),
+    /// `tag:<key>` = value
+    Tag {
+        key: String,
+        value: String,
+    },
+    VpcId(String),
+    SubnetId(String),
+    SecurityGroupId(String),
 }
 
+impl InstanceFilter {
+    fn into_pair(self) -> (String, String) {
+        match self {
+            Self::Zone(v) => ("zone".into(), v),
+            Self::InstanceState(v) => ("instance-state".into(), v.to_string()),
+            Self::InstanceName(v) => ("instance-name".into(), v),
+            Self::InstanceChargeType(v) => ("instance-charge-type".into(), v.to_string()),
+            Self::PrivateIpAddress(v) => ("private-ip-address".into(), v),
+            Self::PublicIpAddress(v) => ("public-ip-address".into(), v),
+            Self::InstanceId(v) => ("instance-id".into(), v),
+            Self::ProjectId(v) => ("project-id".into(), v.to_string()),
+            Self::TagKey(v) => ("tag-key".into(), v),
+            Self::TagValue(v) => ("tag-value".into(), v),
+            Self::Tag { key, value } => (format!("tag:{key}"), value),
+            Self::VpcId(v) => ("vpc-id".into(), v),
+            Self::SubnetId(v) => ("subnet-id".into(), v),
+            Self::SecurityGroupId(v) => ("security-group-id".into(), v),
+        }
+    }
+}
+
+/// DescribeInstancesResponse
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct DescribeInstancesResponseInner {
+pub struct DescribeInstancesResponse {
+    pub total_count: u64,
     pub instance_set: Vec<Instance>,
+    pub request_id: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Instance {
+    pub instance_id: String,
+    pub instance_name: String,
     pub instance_state: InstanceState,
+    pub instance_type: String,
+    #[serde(rename = "CPU")]
+    pub cpu: u32,
+    /// GB
+    pub memory: u32,
+    pub placement: Placement,
+    pub instance_charge_type: InstanceChargeType,
+    pub image_id: String,
+    #[serde(default)]
+    pub os_name: Option<String>,
+    #[serde(default, deserialize_with = "null_default")]
+    pub private_ip_addresses: Vec<String>,
     pub public_ip_addresses: Option<Vec<String>>,
-    pub instance_id: String,
-    // todo more
+    #[serde(default, rename = "IPv6Addresses")]
+    pub ipv6_addresses: Option<Vec<String>>,
+    pub system_disk: SystemDisk,
+    #[serde(default)]
+    pub data_disks: Option<Vec<DataDisk>>,
+    #[serde(default)]
+    pub internet_accessible: Option<InternetAccessible>,
+    #[serde(default)]
+    pub virtual_private_cloud: Option<VirtualPrivateCloud>,
+    #[serde(default, deserialize_with = "null_default")]
+    pub security_group_ids: Vec<String>,
+    #[serde(default)]
+    pub login_settings: Option<LoginSettings>,
+    #[serde(default, deserialize_with = "null_default")]
+    pub tags: Vec<Tag>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_time: OffsetDateTime,
+    /// only for PREPAID instances
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expired_time: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub renew_flag: Option<RenewFlag>,
+    #[serde(default)]
+    pub restrict_state: Option<RestrictState>,
+    #[serde(default)]
+    pub stop_charging_mode: Option<StopChargingMode>,
+    #[serde(default)]
+    pub isolated_source: Option<IsolatedSource>,
+    #[serde(default)]
+    pub latest_operation: Option<String>,
+    #[serde(default)]
+    pub latest_operation_state: Option<LatestOperationState>,
+    #[serde(default)]
+    pub latest_operation_request_id: Option<String>,
+    #[serde(default)]
+    pub latest_operation_error_msg: Option<String>,
+    #[serde(default)]
+    pub uuid: Option<String>,
+    #[serde(default)]
+    pub cam_role_name: Option<String>,
+    #[serde(default)]
+    pub disaster_recover_group_id: Option<String>,
+    #[serde(default)]
+    pub disable_api_termination: Option<bool>,
+    #[serde(default)]
+    pub default_login_user: Option<String>,
+    #[serde(default)]
+    pub default_login_port: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum InstanceChargeType {
+    Prepaid,
+    PostpaidByHour,
+    Cdhpaid,
+    Spotpaid,
+    Cdcpaid,
+    /// returned by the api only, cannot be sent
+    #[serde(other, skip_serializing)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum DiskType {
+    LocalBasic,
+    LocalSsd,
+    CloudBasic,
+    CloudSsd,
+    CloudPremium,
+    CloudBssd,
+    CloudHssd,
+    CloudTssd,
+    /// returned by the api only, cannot be sent
+    #[serde(other, skip_serializing)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum RenewFlag {
+    NotifyAndManualRenew,
+    NotifyAndAutoRenew,
+    DisableNotifyAndManualRenew,
+    /// returned by the api only, cannot be sent
+    #[serde(other, skip_serializing)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum RestrictState {
+    Normal,
+    Expired,
+    ProtectivelyIsolated,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum StopChargingMode {
+    KeepCharging,
+    StopCharging,
+    NotApplicable,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum IsolatedSource {
+    Arrear,
+    Expire,
+    Manmade,
+    Notisolated,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum LatestOperationState {
+    Success,
+    Operating,
+    Failed,
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Display)]
 #[serde(rename_all = "UPPERCASE")]
+#[allow(non_camel_case_types)]
 pub enum InstanceState {
-    PENDING, //表示创建中
-    #[allow(non_camel_case_types)]
-    LAUNCH_FAILED, //表示创建失败
-    RUNNING, //表示运行中
-    STOPPED, //表示关机
-    STARTING, //表示开机中
-    STOPPING, //表示关机中
-    REBOOTING, //表示重启中
-    SHUTDOWN, //表示停止待销毁
-    TERMINATING, //表示销毁中。
-}
-
-/// InquiryPriceRunInstancesResponse
+    PENDING,                    //表示创建中
+    LAUNCH_FAILED,              //表示创建失败
+    RUNNING,                    //表示运行中
+    STOPPED,                    //表示关机
+    STARTING,                   //表示开机中
+    STOPPING,                   //表示关机中
+    REBOOTING,                  //表示重启中
+    SHUTDOWN,                   //表示停止待销毁
+    TERMINATING,                //表示销毁中。
+    ENTER_RESCUE_MODE,          //表示进入救援模式
+    RESCUE_MODE,                //表示在救援模式中
+    EXIT_RESCUE_MODE,           //表示退出救援模式
+    ENTER_SERVICE_LIVE_MIGRATE, //表示进入维护模式
+    SERVICE_LIVE_MIGRATE,       //表示在维护模式中
+    EXIT_SERVICE_LIVE_MIGRATE,  //表示退出维护模式
+    /// a state added to the api after this crate
+    #[serde(other)]
+    UNKNOWN,
+}
+
+/// RunInstancesRequest, see [`RunInstancesRequest::new`] for the builder
+///
+/// `new` generates the `ClientToken` that makes retries idempotent, and a clone keeps it: two
+/// launches of clones are deduplicated into one. call
+/// [`RunInstancesRequest::with_new_client_token`] on a clone used as a template for another batch.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RunInstancesRequest {
+    pub instance_charge_type: InstanceChargeType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_charge_prepaid: Option<InstanceChargePrepaid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_market_options: Option<InstanceMarketOptions>,
+    pub image_id: String,
+    pub placement: Placement,
+    pub instance_type: String,
+    pub instance_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_disk: Option<SystemDisk>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub data_disks: Vec<DataDisk>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virtual_private_cloud: Option<VirtualPrivateCloud>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internet_accessible: Option<InternetAccessible>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login_settings: Option<LoginSettings>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub security_group_ids: Vec<String>,
+    /// base64 encoded, at most 16 KB before encoding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_data: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tag_specification: Vec<TagSpecification>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cam_role_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_token: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InstanceChargePrepaid {
+    /// months
+    pub period: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renew_flag: Option<RenewFlag>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InstanceMarketOptions {
+    /// only `spot`
+    pub market_type: String,
+    pub spot_options: SpotMarketOptions,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SpotMarketOptions {
+    /// yuan per hour
+    pub max_price: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spot_instance_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TagSpecification {
+    /// `instance`
+    pub resource_type: String,
+    pub tags: Vec<Tag>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum InternetChargeType {
+    BandwidthPrepaid,
+    TrafficPostpaidByHour,
+    BandwidthPostpaidByHour,
+    BandwidthPackage,
+    /// returned by the api only, cannot be sent
+    #[serde(other, skip_serializing)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Placement {
+    pub zone: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SystemDisk {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_type: Option<DiskType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_id: Option<String>,
+    /// GB
+    pub disk_size: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DataDisk {
+    /// GB
+    pub disk_size: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_type: Option<DiskType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_with_instance: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypt: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kms_key_id: Option<String>,
+    /// MB/s
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throughput_performance: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InternetAccessible {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internet_charge_type: Option<InternetChargeType>,
+    /// Mbps
+    pub internet_max_bandwidth_out: u32,
+    #[serde(default, deserialize_with = "null_default")]
+    pub public_ip_assigned: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bandwidth_package_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct VirtualPrivateCloud {
+    pub vpc_id: String,
+    pub subnet_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_vpc_gateway: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_ip_addresses: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv6_address_count: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LoginSettings {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// `TRUE` to keep the login settings of the image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_image_login: Option<String>,
+}
+
+impl RunInstancesRequest {
+    /// one POSTPAID_BY_HOUR instance with the defaults of the image, refine with the setters.
+    ///
+    /// a random `ClientToken` is set, so sending the same request again, e.g. after a timeout,
+    /// does not create the instances twice.
+    pub fn new(
+        zone: impl Into<String>,
+        instance_type: impl ToString,
+        image_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            instance_charge_type: InstanceChargeType::PostpaidByHour,
+            instance_charge_prepaid: None,
+            instance_market_options: None,
+            image_id: image_id.into(),
+            placement: Placement {
+                zone: zone.into(),
+                ..Default::default()
+            },
+            instance_type: instance_type.to_string(),
+            instance_count: 1,
+            instance_name: None,
+            host_name: None,
+            system_disk: None,
+            data_disks: vec![],
+            virtual_private_cloud: None,
+            internet_accessible: None,
+            login_settings: None,
+            security_group_ids: vec![],
+            user_data: None,
+            tag_specification: vec![],
+            cam_role_name: None,
+            client_token: Some(Uuid::new_v4().to_string()),
+            dry_run: false,
+        }
+    }
+
+    /// SPOTPAID/Ubuntu2204/20GB disk/10Mbps
+    fn spot_default(zone: &str, instance_type: &InstanceType) -> Self {
+        Self::new(zone, instance_type, "img-487zeit5")
+            .spot(None)
+            .system_disk(None, 20)
+            .internet(InternetChargeType::TrafficPostpaidByHour, 10, true)
+    }
+
+    pub fn charge_type(mut self, charge_type: InstanceChargeType) -> Self {
+        self.instance_charge_type = charge_type;
+        self
+    }
+    /// PREPAID for `period` months
+    pub fn prepaid(mut self, period: u32, renew_flag: Option<RenewFlag>) -> Self {
+        self.instance_charge_type = InstanceChargeType::Prepaid;
+        self.instance_charge_prepaid = Some(InstanceChargePrepaid { period, renew_flag });
+        self
+    }
+    /// SPOTPAID, bidding at most `max_price` yuan per hour, the market price if `None`
+    pub fn spot(mut self, max_price: Option<f64>) -> Self {
+        self.instance_charge_type = InstanceChargeType::Spotpaid;
+        self.instance_market_options = max_price.map(|max_price| InstanceMarketOptions {
+            market_type: "spot".into(),
+            spot_options: SpotMarketOptions {
+                max_price: max_price.to_string(),
+                spot_instance_type: Some("one-time".into()),
+            },
+        });
+        self
+    }
+    pub fn project_id(mut self, project_id: i64) -> Self {
+        self.placement.project_id = Some(project_id);
+        self
+    }
+    pub fn instance_count(mut self, count: u32) -> Self {
+        self.instance_count = count;
+        self
+    }
+    /// with several instances `{R:1}` in the name is replaced by an increasing number
+    pub fn instance_name(mut self, name: impl Into<String>) -> Self {
+        self.instance_name = Some(name.into());
+        self
+    }
+    pub fn host_name(mut self, host_name: impl Into<String>) -> Self {
+        self.host_name = Some(host_name.into());
+        self
+    }
+    /// `size` in GB
+    pub fn system_disk(mut self, disk_type: Option<DiskType>, size: u32) -> Self {
+        self.system_disk = Some(SystemDisk {
+            disk_type,
+            disk_id: None,
+            disk_size: size,
+        });
+        self
+    }
+    pub fn data_disk(mut self, disk: DataDisk) -> Self {
+        self.data_disks.push(disk);
+        self
+    }
+    pub fn vpc(mut self, vpc_id: impl Into<String>, subnet_id: impl Into<String>) -> Self {
+        self.virtual_private_cloud = Some(VirtualPrivateCloud {
+            vpc_id: vpc_id.into(),
+            subnet_id: subnet_id.into(),
+            ..Default::default()
+        });
+        self
+    }
+    /// `max_bandwidth_out` in Mbps
+    pub fn internet(
+        mut self,
+        charge_type: InternetChargeType,
+        max_bandwidth_out: u32,
+        public_ip_assigned: bool,
+    ) -> Self {
+        self.internet_accessible = Some(InternetAccessible {
+            internet_charge_type: Some(charge_type),
+            internet_max_bandwidth_out: max_bandwidth_out,
+            public_ip_assigned,
+            bandwidth_package_id: None,
+        });
+        self
+    }
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.login_settings
+            .get_or_insert_with(Default::default)
+            .password = Some(password.into());
+        self
+    }
+    pub fn key_ids(mut self, key_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.login_settings
+            .get_or_insert_with(Default::default)
+            .key_ids
+            .extend(key_ids.into_iter().map(Into::into));
+        self
+    }
+    pub fn security_group_ids(mut self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.security_group_ids
+            .extend(ids.into_iter().map(Into::into));
+        self
+    }
+    /// raw user data, base64 encoded here
+    pub fn user_data(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.user_data = Some(STANDARD.encode(data));
+        self
+    }
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let tag = Tag {
+            key: key.into(),
+            value: value.into(),
+        };
+        match self
+            .tag_specification
+            .iter_mut()
+            .find(|spec| spec.resource_type == "instance")
+        {
+            Some(spec) => spec.tags.push(tag),
+            None => self.tag_specification.push(TagSpecification {
+                resource_type: "instance".into(),
+                tags: vec![tag],
+            }),
+        }
+        self
+    }
+    pub fn cam_role_name(mut self, role: impl Into<String>) -> Self {
+        self.cam_role_name = Some(role.into());
+        self
+    }
+    /// replace the generated token, at most 64 ascii characters
+    pub fn client_token(mut self, token: impl Into<String>) -> Self {
+        self.client_token = Some(token.into());
+        self
+    }
+    /// same request with a freshly generated token, to launch another batch from a template
+    pub fn with_new_client_token(self) -> Self {
+        self.client_token(Uuid::new_v4().to_string())
+    }
+    /// the request as a dry run, without `ClientToken` so the real launch is not deduped
+    fn to_dry_run(&self) -> Self {
+        Self {
+            client_token: None,
+            dry_run: true,
+            ..self.clone()
+        }
+    }
+}
+
+/// RunInstancesResponse
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct InquiryPriceRunInstancesResponse {
-    pub response: InquiryPriceRunInstancesResponseInner,
+pub struct RunInstancesResponse {
+    pub instance_id_set: Vec<String>,
+    pub request_id: String,
+}
+
+/// InquiryPriceRunInstancesRequest, same body as [`RunInstancesRequest`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(transparent)]
+pub struct InquiryPriceRunInstancesRequest(pub RunInstancesRequest);
+
+impl From<RunInstancesRequest> for InquiryPriceRunInstancesRequest {
+    /// drops `ClientToken`/`DryRun`, which price inquiry does not take
+    fn from(request: RunInstancesRequest) -> Self {
+        Self(RunInstancesRequest {
+            client_token: None,
+            dry_run: false,
+            ..request
+        })
+    }
+}
+
+/// InquiryPriceResetInstanceRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InquiryPriceResetInstanceRequest {
+    pub instance_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_disk: Option<SystemDisk>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login_settings: Option<LoginSettings>,
+}
+
+/// InquiryPriceResizeInstanceDisksRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InquiryPriceResizeInstanceDisksRequest {
+    pub instance_id: String,
+    /// `disk_id` and the new `disk_size` of each disk
+    pub data_disks: Vec<DataDisk>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_stop: Option<bool>,
 }
+
+/// InquiryPriceModifyInstancesChargeTypeRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InquiryPriceModifyInstancesChargeTypeRequest {
+    pub instance_ids: Vec<String>,
+    pub instance_charge_type: InstanceChargeType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_charge_prepaid: Option<InstanceChargePrepaid>,
+}
+
+/// response of all InquiryPrice* actions
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct InquiryPriceRunInstancesResponseInner {
+pub struct InquiryPriceResponse {
     pub price: Price,
+    pub request_id: String,
 }
-#[derive(Debug, Deserialize)]
+
+pub type InquiryPriceRunInstancesResponse = InquiryPriceResponse;
+
+/// prepaid quotes fill the `*_price` totals, postpaid ones the `unit_price*` hourly steps.
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Price {
-    pub instance_price: PriceDetail,
-    pub bandwidth_price: PriceDetail,
+    #[serde(default)]
+    pub instance_price: Option<PriceDetail>,
+    #[serde(default)]
+    pub bandwidth_price: Option<PriceDetail>,
 }
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
 pub struct PriceDetail {
-    pub unit_price: f64,
-    pub unit_price_discount: f64,
-    pub charge_unit: String,
-    pub discount: f64,
+    pub unit_price: Option<f64>,
+    pub unit_price_discount: Option<f64>,
+    pub charge_unit: Option<String>,
+    pub original_price: Option<f64>,
+    pub discount_price: Option<f64>,
+    pub discount: Option<f64>,
+    /// price after `UnitPrice` ran 96 hours
+    pub unit_price_second_step: Option<f64>,
+    pub unit_price_discount_second_step: Option<f64>,
+    /// price after `UnitPrice` ran 360 hours
+    pub unit_price_third_step: Option<f64>,
+    pub unit_price_discount_third_step: Option<f64>,
+    pub original_price_one_year: Option<f64>,
+    pub discount_price_one_year: Option<f64>,
+    pub discount_one_year: Option<f64>,
+    pub original_price_three_year: Option<f64>,
+    pub discount_price_three_year: Option<f64>,
+    pub discount_three_year: Option<f64>,
+    pub original_price_five_year: Option<f64>,
+    pub discount_price_five_year: Option<f64>,
+    pub discount_five_year: Option<f64>,
 }
 
-/// RunInstancesResponse
+/// TerminateInstancesRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TerminateInstancesRequest {
+    pub instance_ids: Vec<String>,
+}
+
+/// TerminateInstancesResponse
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct RunInstancesResponse {
-    pub response: RunInstancesResponseInner,
+pub struct TerminateInstancesResponse {
+    pub request_id: String,
 }
 
+/// response of the actions returning only `RequestId`, e.g. StartInstances
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct RunInstancesResponseInner {
-    pub instance_id_set: Vec<String>,
+pub struct OperationResponse {
+    pub request_id: String,
+}
+
+/// StartInstancesRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StartInstancesRequest {
+    pub instance_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum StopType {
+    Soft,
+    Hard,
+    /// soft, then hard if the soft stop fails
+    SoftFirst,
+}
+
+/// `StoppedMode` of StopInstances, see [`StopChargingMode`] for the state of an instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum StoppedMode {
+    KeepCharging,
+    /// only for POSTPAID_BY_HOUR instances in a vpc
+    StopCharging,
 }
 
-const DESCRIBE_INSTANCES: &str = "DescribeInstances";
-const INQUIRY_PRICE_RUN_INSTANCES: &str = "InquiryPriceRunInstances";
-const RUN_INSTANCES: &str = "RunInstances";
-const TERMINATE_INSTANCES: &str = "TerminateInstances";
+/// StopInstancesRequest
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StopInstancesRequest {
+    pub instance_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_type: Option<StopType>,
+    /// `StopCharging` to stop billing of POSTPAID_BY_HOUR instances in a vpc
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stopped_mode: Option<StoppedMode>,
+}
+
+/// RebootInstancesRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RebootInstancesRequest {
+    pub instance_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_type: Option<StopType>,
+}
+
+/// ResetInstanceRequest, reinstalls the system disk
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ResetInstanceRequest {
+    pub instance_id: String,
+    /// the current image if `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_disk: Option<SystemDisk>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login_settings: Option<LoginSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_name: Option<String>,
+}
+
+/// ResetInstancesTypeRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ResetInstancesTypeRequest {
+    pub instance_ids: Vec<String>,
+    pub instance_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_stop: Option<bool>,
+}
+
+/// ResetInstancesPasswordRequest
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ResetInstancesPasswordRequest {
+    pub instance_ids: Vec<String>,
+    pub password: String,
+    /// `root`/`ubuntu`/`Administrator` by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_name: Option<String>,
+    /// running instances must be stopped to reset the password
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_stop: Option<bool>,
+}
+
+/// ModifyInstancesAttributeRequest, only the set attributes are modified
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ModifyInstancesAttributeRequest {
+    pub instance_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_name: Option<String>,
+    /// replaces all security groups
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_groups: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cam_role_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_api_termination: Option<bool>,
+}
+
+/// ModifyInstancesProjectRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ModifyInstancesProjectRequest {
+    pub instance_ids: Vec<String>,
+    pub project_id: i64,
+}
+
+impl_action!(SERVICE, VERSION, "DescribeInstances", DescribeInstancesRequest => DescribeInstancesResponse);
+impl_action!(SERVICE, VERSION, "InquiryPriceRunInstances", InquiryPriceRunInstancesRequest => InquiryPriceResponse);
+impl_action!(SERVICE, VERSION, "InquiryPriceResetInstance", InquiryPriceResetInstanceRequest => InquiryPriceResponse);
+impl_action!(SERVICE, VERSION, "InquiryPriceResizeInstanceDisks", InquiryPriceResizeInstanceDisksRequest => InquiryPriceResponse);
+impl_action!(SERVICE, VERSION, "InquiryPriceModifyInstancesChargeType", InquiryPriceModifyInstancesChargeTypeRequest => InquiryPriceResponse);
+impl_action!(SERVICE, VERSION, "RunInstances", RunInstancesRequest => RunInstancesResponse);
+impl_action!(SERVICE, VERSION, "TerminateInstances", TerminateInstancesRequest => TerminateInstancesResponse);
+impl_action!(SERVICE, VERSION, "StartInstances", StartInstancesRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "StopInstances", StopInstancesRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "RebootInstances", RebootInstancesRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "ResetInstance", ResetInstanceRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "ResetInstancesType", ResetInstancesTypeRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "ResetInstancesPassword", ResetInstancesPasswordRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "ModifyInstancesAttribute", ModifyInstancesAttributeRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "ModifyInstancesProject", ModifyInstancesProjectRequest => OperationResponse);
+impl_paginate!(DescribeInstancesRequest, instance_set: Instance);
 
 impl CVMInstanceBuilder {
     pub fn new(client: Arc<TencentCloudBaseClient>) -> Self {
-        Self {
-            client,
-            service_name: "cvm".into(),
-            version: "2017-03-12".into(),
-        }
+        Self { client }
+    }
+    /// all instances of the region, walking every page
+    pub async fn describe_instance(&self, region: &Region) -> Result<Vec<Instance>> {
+        self.describe_instance_pages(region, DescribeInstancesRequest::default())
+            .collect_all()
+            .await
     }
-    pub async fn describe_instance(
+
+    /// all instances matching the ids or filters of `request`
+    pub async fn describe_instances(
         &self,
         region: &Region,
-    ) -> anyhow::Result<DescribeInstancesResponse> {
-        let resp = self
-            .client
-            .post(&self.service_name, &self.version)
-            .header(ACTION_HEADER, DESCRIBE_INSTANCES)
-            .header(REGION_HEADER, region.to_string())
-            .json(&json!({}))
-            .send()
-            .await?;
-
-        match resp.status() {
-            StatusCode::OK => Ok(resp.json().await?),
-            rest => Err(anyhow::anyhow!(
-                "err get code {rest}, msg {}",
-                resp.text().await?
-            )),
-        }
+        request: DescribeInstancesRequest,
+    ) -> Result<Vec<Instance>> {
+        self.describe_instance_pages(region, request)
+            .collect_all()
+            .await
+    }
+
+    pub async fn describe_instance_by_id(
+        &self,
+        region: &Region,
+        instance_id: &str,
+    ) -> Result<Option<Instance>> {
+        let request = DescribeInstancesRequest::default().instance_ids([instance_id]);
+        let body = self.client.execute(region, &request).await?;
+        Ok(body.instance_set.into_iter().next())
+    }
+
+    pub fn describe_instance_pages(
+        &self,
+        region: &Region,
+        request: DescribeInstancesRequest,
+    ) -> Paginator<DescribeInstancesRequest> {
+        Paginator::new(self.client.clone(), region.clone(), request)
     }
 
     /// set default SPOTPAID/Ubuntu2204/20GB disk
@@ -133,43 +951,48 @@ impl CVMInstanceBuilder {
         region: &Region,
         zone: &str,
         instance_type: &InstanceType,
-    ) -> anyhow::Result<Price> {
-        let resp = self
-            .client
-            .post(&self.service_name, &self.version)
-            .header(ACTION_HEADER, INQUIRY_PRICE_RUN_INSTANCES)
-            .header(REGION_HEADER, region.to_string())
-            .json(&json!({
-                "InstanceChargeType": "SPOTPAID",
-                "ImageId": "img-487zeit5",
-                "Placement": {
-                    "Zone": zone
-                },
-                "InstanceType": instance_type.to_string(),
-                "InstanceCount": 1,
-                "SystemDisk": {
-                    "DiskSize": 20,
-                },
-                "InternetAccessible": {
-                    "InternetChargeType": "TRAFFIC_POSTPAID_BY_HOUR",
-                    "InternetMaxBandwidthOut": 10,
-                    "PublicIpAssigned": true
-                }
-            }))
-            .send()
-            .await?;
-
-        match resp.status() {
-            StatusCode::OK => {
-                let body: InquiryPriceRunInstancesResponse = resp.json().await?;
-                debug!("body: {body:?}");
-                Ok(body.response.price)
-            }
-            rest => Err(anyhow::anyhow!(
-                "err get code {rest}, msg {}",
-                resp.text().await?
-            )),
-        }
+    ) -> Result<Price> {
+        let request = RunInstancesRequest::spot_default(zone, instance_type);
+        self.query_price_run_instances(region, &request).await
+    }
+
+    /// price of launching `request`
+    pub async fn query_price_run_instances(
+        &self,
+        region: &Region,
+        request: &RunInstancesRequest,
+    ) -> Result<Price> {
+        let request = InquiryPriceRunInstancesRequest::from(request.clone());
+        let body = self.client.execute(region, &request).await?;
+        Ok(body.price)
+    }
+
+    /// price of reinstalling an instance, e.g. with a larger system disk
+    pub async fn query_price_reset_instance(
+        &self,
+        region: &Region,
+        request: &InquiryPriceResetInstanceRequest,
+    ) -> Result<Price> {
+        let body = self.client.execute(region, request).await?;
+        Ok(body.price)
+    }
+
+    pub async fn query_price_resize_instance_disks(
+        &self,
+        region: &Region,
+        request: &InquiryPriceResizeInstanceDisksRequest,
+    ) -> Result<Price> {
+        let body = self.client.execute(region, request).await?;
+        Ok(body.price)
+    }
+
+    pub async fn query_price_modify_charge_type(
+        &self,
+        region: &Region,
+        request: &InquiryPriceModifyInstancesChargeTypeRequest,
+    ) -> Result<Price> {
+        let body = self.client.execute(region, request).await?;
+        Ok(body.price)
     }
 
     /// set default SPOTPAID/Ubuntu2204/20GB disk
@@ -180,86 +1003,390 @@ impl CVMInstanceBuilder {
         instance_type: &InstanceType,
         key_ids: Vec<String>,
         security_group: Vec<String>,
-    ) -> anyhow::Result<String> {
-        let mut body = json!({
-            "InstanceChargeType": "SPOTPAID",
-            "ImageId": "img-487zeit5",
-            "Placement": {
-                "Zone": zone
-            },
-            "InstanceType": instance_type.to_string(),
-            "InstanceCount": 1,
-            "SystemDisk": {
-                "DiskSize": 20,
-            },
-            "InternetAccessible": {
-                "InternetChargeType": "TRAFFIC_POSTPAID_BY_HOUR",
-                "InternetMaxBandwidthOut": 10,
-                "PublicIpAssigned": true
-            }
-        });
+    ) -> Result<String> {
+        let mut request = RunInstancesRequest::spot_default(zone, instance_type)
+            .security_group_ids(security_group);
         if !key_ids.is_empty() {
-            body["LoginSettings"] = json!({
-                "KeyIds": key_ids
-            });
+            request = request.key_ids(key_ids);
         }
-        if !security_group.is_empty() {
-            body["SecurityGroupIds"] = security_group.into();
+        let body = self.client.execute(region, &request).await?;
+        match body.instance_id_set.first() {
+            Some(id) => Ok(id.clone()),
+            None => Err(TencentCloudError::Decode {
+                source: serde::de::Error::custom("response missing InstanceIdSet"),
+                body: format!("{body:?}"),
+            }),
         }
-        let resp = self
-            .client
-            .post(&self.service_name, &self.version)
-            .header(ACTION_HEADER, RUN_INSTANCES)
-            .header(REGION_HEADER, region.to_string())
-            .json(&body)
-            .send()
-            .await?;
-
-        match resp.status() {
-            StatusCode::OK => {
-                let body = resp.text().await?;
-                debug!("body: {body:?}");
-                let body: RunInstancesResponse = serde_json::from_str(&body)?;
-                debug!("body: {body:?}");
-                body.response
-                    .instance_id_set
-                    .into_iter()
-                    .nth(0)
-                    .ok_or(anyhow::anyhow!("panic!! response missing id ???"))
-            }
-            rest => Err(anyhow::anyhow!(
-                "err get code {rest}, msg {}",
-                resp.text().await?
-            )),
+    }
+
+    /// check `request` without launching anything, `Ok` when it would have succeeded.
+    pub async fn dry_run(&self, region: &Region, request: &RunInstancesRequest) -> Result<()> {
+        match self.client.execute(region, &request.to_dry_run()).await {
+            Err(e) if e.error_code() == Some(ErrorCode::DryRunOperation) => Ok(()),
+            Err(e) => Err(e),
+            Ok(body) => Err(TencentCloudError::Decode {
+                source: serde::de::Error::custom("dry run launched instances"),
+                body: format!("{body:?}"),
+            }),
         }
     }
 
-    pub async fn terminate_instance(
+    /// ids of all created instances
+    pub async fn run_instances(
         &self,
         region: &Region,
-        instance_id: &str,
-    ) -> anyhow::Result<()> {
-        let resp = self
-            .client
-            .post(&self.service_name, &self.version)
-            .header(ACTION_HEADER, TERMINATE_INSTANCES)
-            .header(REGION_HEADER, region.to_string())
-            .json(&json!({
-                "InstanceIds": [instance_id]
-            }))
-            .send()
-            .await?;
-
-        match resp.status() {
-            StatusCode::OK => {
-                let body = resp.text().await;
-                debug!("body: {body:?}");
-                Ok(())
-            }
-            rest => Err(anyhow::anyhow!(
-                "err get code {rest}, msg {}",
-                resp.text().await?
-            )),
+        request: &RunInstancesRequest,
+    ) -> Result<Vec<String>> {
+        let body = self.client.execute(region, request).await?;
+        Ok(body.instance_id_set)
+    }
+
+    pub async fn terminate_instance(&self, region: &Region, instance_id: &str) -> Result<()> {
+        self.terminate_instances(region, [instance_id]).await
+    }
+
+    pub async fn terminate_instances(
+        &self,
+        region: &Region,
+        instance_ids: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<()> {
+        let request = TerminateInstancesRequest {
+            instance_ids: ids(instance_ids),
+        };
+        self.client.execute(region, &request).await?;
+        Ok(())
+    }
+
+    pub async fn start_instances(
+        &self,
+        region: &Region,
+        instance_ids: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<()> {
+        let request = StartInstancesRequest {
+            instance_ids: ids(instance_ids),
+        };
+        self.client.execute(region, &request).await?;
+        Ok(())
+    }
+
+    pub async fn stop_instances(
+        &self,
+        region: &Region,
+        request: &StopInstancesRequest,
+    ) -> Result<()> {
+        self.client.execute(region, request).await?;
+        Ok(())
+    }
+
+    pub async fn reboot_instances(
+        &self,
+        region: &Region,
+        instance_ids: impl IntoIterator<Item = impl Into<String>>,
+        stop_type: Option<StopType>,
+    ) -> Result<()> {
+        let request = RebootInstancesRequest {
+            instance_ids: ids(instance_ids),
+            stop_type,
+        };
+        self.client.execute(region, &request).await?;
+        Ok(())
+    }
+
+    /// reinstall the instance, with a new image if set
+    pub async fn reset_instance(
+        &self,
+        region: &Region,
+        request: &ResetInstanceRequest,
+    ) -> Result<()> {
+        self.client.execute(region, request).await?;
+        Ok(())
+    }
+
+    /// change the instance type, instances must be stopped unless `force_stop`
+    pub async fn reset_instances_type(
+        &self,
+        region: &Region,
+        instance_ids: impl IntoIterator<Item = impl Into<String>>,
+        instance_type: impl ToString,
+        force_stop: bool,
+    ) -> Result<()> {
+        let request = ResetInstancesTypeRequest {
+            instance_ids: ids(instance_ids),
+            instance_type: instance_type.to_string(),
+            force_stop: force_stop.then_some(true),
+        };
+        self.client.execute(region, &request).await?;
+        Ok(())
+    }
+
+    pub async fn reset_instances_password(
+        &self,
+        region: &Region,
+        request: &ResetInstancesPasswordRequest,
+    ) -> Result<()> {
+        self.client.execute(region, request).await?;
+        Ok(())
+    }
+
+    pub async fn modify_instances_attribute(
+        &self,
+        region: &Region,
+        request: &ModifyInstancesAttributeRequest,
+    ) -> Result<()> {
+        self.client.execute(region, request).await?;
+        Ok(())
+    }
+
+    pub async fn modify_instances_project(
+        &self,
+        region: &Region,
+        instance_ids: impl IntoIterator<Item = impl Into<String>>,
+        project_id: i64,
+    ) -> Result<()> {
+        let request = ModifyInstancesProjectRequest {
+            instance_ids: ids(instance_ids),
+            project_id,
+        };
+        self.client.execute(region, &request).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_describe_instances_filters() {
+        let request = DescribeInstancesRequest::default()
+            .filter(InstanceFilter::Zone("ap-guangzhou-3".into()))
+            .filter(InstanceFilter::Zone("ap-guangzhou-4".into()))
+            .filter(InstanceFilter::InstanceState(InstanceState::LAUNCH_FAILED))
+            .filter(InstanceFilter::Tag {
+                key: "env".into(),
+                value: "prod".into(),
+            });
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({"Filters": [
+                {"Name": "zone", "Values": ["ap-guangzhou-3", "ap-guangzhou-4"]},
+                {"Name": "instance-state", "Values": ["LAUNCH_FAILED"]},
+                {"Name": "tag:env", "Values": ["prod"]},
+            ]})
+        );
+
+        let request = DescribeInstancesRequest::default().instance_ids(["ins-1"]);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({"InstanceIds": ["ins-1"]})
+        );
+    }
+
+    #[test]
+    fn test_decode_instance() {
+        let body = json!({
+            "InstanceId": "ins-xlsmp4xy",
+            "InstanceName": "web",
+            "InstanceState": "RUNNING",
+            "InstanceType": "S5.MEDIUM4",
+            "CPU": 2,
+            "Memory": 4,
+            "Placement": {"Zone": "ap-guangzhou-3", "ProjectId": 0, "HostId": null},
+            "InstanceChargeType": "POSTPAID_BY_HOUR",
+            "ImageId": "img-487zeit5",
+            "OsName": "Ubuntu Server 22.04 LTS 64bit",
+            "PrivateIpAddresses": ["172.16.0.31"],
+            "PublicIpAddresses": null,
+            "IPv6Addresses": null,
+            "SystemDisk": {"DiskType": "CLOUD_PREMIUM", "DiskId": "disk-1", "DiskSize": 50},
+            "DataDisks": [{"DiskType": "CLOUD_HSSD", "DiskId": "disk-2", "DiskSize": 100,
+                "DeleteWithInstance": true, "Encrypt": false, "ThroughputPerformance": 0}],
+            "InternetAccessible": {"InternetChargeType": "TRAFFIC_POSTPAID_BY_HOUR",
+                "InternetMaxBandwidthOut": 10},
+            "VirtualPrivateCloud": {"VpcId": "vpc-1", "SubnetId": "subnet-1",
+                "AsVpcGateway": false},
+            "SecurityGroupIds": ["sg-1"],
+            "LoginSettings": {"KeyIds": ["skey-1"]},
+            "Tags": [{"Key": "env", "Value": "prod"}],
+            "CreatedTime": "2020-03-10T02:43:51Z",
+            "ExpiredTime": null,
+            "RenewFlag": null,
+            "RestrictState": "NORMAL",
+            "StopChargingMode": "NOT_APPLICABLE",
+            "IsolatedSource": "NOTISOLATED",
+            "LatestOperation": "StartInstances",
+            "LatestOperationState": "SUCCESS",
+            "LatestOperationRequestId": "c7de1287-061d-4ace-8caf-6ad8e5a2f29a",
+            "Uuid": "e85f1388-0422-410d-8e50-bef540e78c18",
+            "DisableApiTermination": false,
+            "DefaultLoginUser": "ubuntu",
+            "DefaultLoginPort": 22,
+            "SomethingNew": "ignored"
+        });
+        let instance: Instance = serde_json::from_value(body.clone()).unwrap();
+        assert_eq!(instance.cpu, 2);
+        assert_eq!(
+            instance.instance_charge_type,
+            InstanceChargeType::PostpaidByHour
+        );
+        assert_eq!(instance.system_disk.disk_type, Some(DiskType::CloudPremium));
+        assert_eq!(instance.data_disks.unwrap()[0].disk_size, 100);
+        assert_eq!(instance.created_time.unix_timestamp(), 1583808231);
+        assert!(instance.expired_time.is_none());
+        assert_eq!(
+            instance.latest_operation_state,
+            Some(LatestOperationState::Success)
+        );
+        assert_eq!(instance.tags[0].key, "env");
+
+        // rescue mode and states newer than this crate do not fail the page
+        for (state, expected) in [
+            ("RESCUE_MODE", InstanceState::RESCUE_MODE),
+            (
+                "ENTER_SERVICE_LIVE_MIGRATE",
+                InstanceState::ENTER_SERVICE_LIVE_MIGRATE,
+            ),
+            ("SOMETHING_NEW", InstanceState::UNKNOWN),
+        ] {
+            let mut body = body.clone();
+            body["InstanceState"] = json!(state);
+            let instance: Instance = serde_json::from_value(body).unwrap();
+            assert_eq!(instance.instance_state, expected);
         }
+
+        // empty lists and unset values come back as null
+        let mut body = body;
+        for key in ["PrivateIpAddresses", "SecurityGroupIds", "Tags"] {
+            body[key] = json!(null);
+        }
+        body["InternetAccessible"] = json!({"InternetChargeType": null,
+            "InternetMaxBandwidthOut": 0, "PublicIpAssigned": null});
+        let instance: Instance = serde_json::from_value(body).unwrap();
+        assert!(instance.private_ip_addresses.is_empty());
+        assert!(instance.security_group_ids.is_empty());
+        assert!(instance.tags.is_empty());
+        let internet = instance.internet_accessible.unwrap();
+        assert!(internet.internet_charge_type.is_none());
+        assert!(!internet.public_ip_assigned);
+    }
+
+    #[test]
+    fn test_run_instances_request() {
+        let request = RunInstancesRequest::new("ap-guangzhou-3", "S5.MEDIUM4", "img-1")
+            .prepaid(1, Some(RenewFlag::NotifyAndAutoRenew))
+            .system_disk(Some(DiskType::CloudPremium), 50)
+            .vpc("vpc-1", "subnet-1")
+            .key_ids(["skey-1"])
+            .user_data("#!/bin/sh")
+            .tag("env", "prod")
+            .tag("team", "infra");
+        let token = request.client_token.clone().unwrap();
+        assert_eq!(Uuid::parse_str(&token).unwrap().get_version_num(), 4);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "ClientToken": token,
+                "InstanceChargeType": "PREPAID",
+                "InstanceChargePrepaid": {"Period": 1, "RenewFlag": "NOTIFY_AND_AUTO_RENEW"},
+                "ImageId": "img-1",
+                "Placement": {"Zone": "ap-guangzhou-3"},
+                "InstanceType": "S5.MEDIUM4",
+                "InstanceCount": 1,
+                "SystemDisk": {"DiskType": "CLOUD_PREMIUM", "DiskSize": 50},
+                "VirtualPrivateCloud": {"VpcId": "vpc-1", "SubnetId": "subnet-1"},
+                "LoginSettings": {"KeyIds": ["skey-1"]},
+                "UserData": "IyEvYmluL3No",
+                "TagSpecification": [{"ResourceType": "instance", "Tags": [
+                    {"Key": "env", "Value": "prod"},
+                    {"Key": "team", "Value": "infra"},
+                ]}],
+            })
+        );
+
+        let request =
+            RunInstancesRequest::new("ap-guangzhou-3", "S5.MEDIUM4", "img-1").spot(Some(0.5));
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(body["InstanceChargeType"], "SPOTPAID");
+        assert_eq!(
+            body["InstanceMarketOptions"],
+            json!({"MarketType": "spot", "SpotOptions": {"MaxPrice": "0.5", "SpotInstanceType": "one-time"}})
+        );
+
+        let request = request.to_dry_run();
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(body["DryRun"], true);
+        assert!(body.get("ClientToken").is_none());
+        assert!(
+            RunInstancesRequest::new("ap-guangzhou-3", "S5.MEDIUM4", "img-1")
+                .client_token("token-1")
+                .to_dry_run()
+                .client_token
+                .is_none()
+        );
+        let body = serde_json::to_value(InquiryPriceRunInstancesRequest::from(request)).unwrap();
+        assert!(body.get("ClientToken").is_none());
+        assert!(body.get("DryRun").is_none());
+
+        // clones share the token, unless a new one is generated
+        let template = RunInstancesRequest::new("ap-guangzhou-3", "S5.MEDIUM4", "img-1");
+        assert_eq!(template.clone().client_token, template.client_token);
+        let other = template.clone().with_new_client_token();
+        assert!(other.client_token.is_some());
+        assert_ne!(other.client_token, template.client_token);
+
+        // response only values are refused instead of sent as "UNKNOWN"
+        let request = RunInstancesRequest::new("ap-guangzhou-3", "S5.MEDIUM4", "img-1")
+            .charge_type(InstanceChargeType::Unknown);
+        assert!(serde_json::to_value(&request).is_err());
+    }
+
+    #[test]
+    fn test_decode_price() {
+        let postpaid: Price = serde_json::from_value(json!({
+            "InstancePrice": {"UnitPrice": 0.39, "UnitPriceDiscount": 0.39, "Discount": 100,
+                "ChargeUnit": "HOUR", "UnitPriceSecondStep": 0.28, "UnitPriceThirdStep": 0.2},
+            "BandwidthPrice": {"UnitPriceDiscount": 0.8, "Discount": 100, "ChargeUnit": "GB",
+                "UnitPrice": 0.8}
+        }))
+        .unwrap();
+        let instance = postpaid.instance_price.unwrap();
+        assert_eq!(instance.unit_price_second_step, Some(0.28));
+        assert!(instance.original_price.is_none());
+
+        let prepaid: Price = serde_json::from_value(json!({
+            "InstancePrice": {"OriginalPrice": 105.2, "DiscountPrice": 105.2, "Discount": 100}
+        }))
+        .unwrap();
+        assert_eq!(prepaid.instance_price.unwrap().discount_price, Some(105.2));
+        assert!(prepaid.bandwidth_price.is_none());
+    }
+
+    #[test]
+    fn test_lifecycle_requests() {
+        let request = StopInstancesRequest {
+            instance_ids: ids(["ins-1", "ins-2"]),
+            stop_type: Some(StopType::SoftFirst),
+            stopped_mode: Some(StoppedMode::StopCharging),
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "InstanceIds": ["ins-1", "ins-2"],
+                "StopType": "SOFT_FIRST",
+                "StoppedMode": "STOP_CHARGING",
+            })
+        );
+
+        let request = ModifyInstancesAttributeRequest {
+            instance_ids: ids(["ins-1"]),
+            security_groups: Some(ids(["sg-1"])),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({"InstanceIds": ["ins-1"], "SecurityGroups": ["sg-1"]})
+        );
     }
 }
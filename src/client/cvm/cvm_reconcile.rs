@@ -0,0 +1,642 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constant::Region,
+    error::{Result, TencentCloudError},
+    error_code::ErrorCode,
+};
+
+use super::cvm_security_group::{
+    AddressTemplateSpecification, ModifySecurityGroupPoliciesRequest, PolicyAction, Protocol,
+    ReplaceSecurityGroupPoliciesRequest, SecurityGroupBuilder, SecurityGroupPolicy,
+    SecurityGroupPolicySet, ServiceTemplateSpecification,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Ingress,
+    Egress,
+}
+
+/// A desired rule, e.g. loaded from yaml:
+///
+/// ```yaml
+/// - direction: ingress
+///   protocol: TCP
+///   port: "80,443"
+///   cidr_block: 0.0.0.0/0
+///   action: ACCEPT
+///   description: web
+/// ```
+///
+/// `address_template` and `service_template` take a template (`ipm-`, `ppm-`) or template
+/// group (`ipmg-`, `ppmg-`) id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Policy {
+    pub direction: Direction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<Protocol>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cidr_block: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv6_cidr_block: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_group_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_template: Option<String>,
+    pub action: PolicyAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl From<Policy> for SecurityGroupPolicy {
+    fn from(policy: Policy) -> Self {
+        let address_template = policy.address_template.map(|id| {
+            if id.starts_with("ipmg-") {
+                AddressTemplateSpecification {
+                    address_group_id: Some(id),
+                    ..Default::default()
+                }
+            } else {
+                AddressTemplateSpecification {
+                    address_id: Some(id),
+                    ..Default::default()
+                }
+            }
+        });
+        let service_template = policy.service_template.map(|id| {
+            if id.starts_with("ppmg-") {
+                ServiceTemplateSpecification {
+                    service_group_id: Some(id),
+                    ..Default::default()
+                }
+            } else {
+                ServiceTemplateSpecification {
+                    service_id: Some(id),
+                    ..Default::default()
+                }
+            }
+        });
+        normalize(SecurityGroupPolicy {
+            policy_index: None,
+            protocol: policy.protocol,
+            port: policy.port,
+            service_template,
+            cidr_block: policy.cidr_block,
+            ipv6_cidr_block: policy.ipv6_cidr_block,
+            security_group_id: policy.security_group_id,
+            address_template,
+            action: policy.action,
+            policy_description: policy.description,
+            modify_time: None,
+        })
+    }
+}
+
+/// the api fills unset fields with `""` and ports of portless rules with `ALL`.
+fn normalize(policy: SecurityGroupPolicy) -> SecurityGroupPolicy {
+    fn some(value: Option<String>) -> Option<String> {
+        value.map(|v| v.trim().to_owned()).filter(|v| !v.is_empty())
+    }
+    let address_template = policy
+        .address_template
+        .map(|t| AddressTemplateSpecification {
+            address_id: some(t.address_id),
+            address_group_id: some(t.address_group_id),
+        })
+        .filter(|t| *t != AddressTemplateSpecification::default());
+    let service_template = policy
+        .service_template
+        .map(|t| ServiceTemplateSpecification {
+            service_id: some(t.service_id),
+            service_group_id: some(t.service_group_id),
+        })
+        .filter(|t| *t != ServiceTemplateSpecification::default());
+    let port = some(policy.port)
+        .map(|port| port.replace(' ', "").to_uppercase())
+        .or_else(|| policy.protocol.map(|_| "ALL".into()));
+    SecurityGroupPolicy {
+        policy_index: None,
+        protocol: policy.protocol,
+        port,
+        service_template,
+        cidr_block: some(policy.cidr_block),
+        ipv6_cidr_block: some(policy.ipv6_cidr_block),
+        security_group_id: some(policy.security_group_id),
+        address_template,
+        action: policy.action,
+        policy_description: some(policy.policy_description),
+        modify_time: None,
+    }
+}
+
+/// Changes of one direction, rules are compared without index and modify time.
+#[derive(Debug, Clone, Default)]
+pub struct DirectionPlan {
+    /// `false` for a direction that is not reconciled, its rules are kept as they are.
+    pub managed: bool,
+    pub current: Vec<SecurityGroupPolicy>,
+    pub desired: Vec<SecurityGroupPolicy>,
+    pub added: Vec<SecurityGroupPolicy>,
+    pub removed: Vec<SecurityGroupPolicy>,
+}
+
+impl DirectionPlan {
+    fn new(current: Vec<SecurityGroupPolicy>, desired: Option<Vec<SecurityGroupPolicy>>) -> Self {
+        let current: Vec<_> = current.into_iter().map(normalize).collect();
+        let Some(desired) = desired else {
+            return Self {
+                managed: false,
+                desired: current.clone(),
+                current,
+                ..Default::default()
+            };
+        };
+        let mut removed = current.clone();
+        let mut added = vec![];
+        for policy in &desired {
+            match removed.iter().position(|p| p == policy) {
+                Some(i) => {
+                    removed.remove(i);
+                }
+                None => added.push(policy.clone()),
+            }
+        }
+        Self {
+            managed: true,
+            current,
+            desired,
+            added,
+            removed,
+        }
+    }
+
+    /// same rules in another order, which changes the first matching rule
+    pub fn reordered(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.current != self.desired
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.current == self.desired
+    }
+}
+
+/// Result of [`SecurityGroupBuilder::plan`], printable before [`SecurityGroupBuilder::apply`].
+#[derive(Debug, Clone)]
+pub struct ReconcilePlan {
+    pub security_group_id: String,
+    /// `Version` of the rules the plan was computed from
+    pub version: String,
+    pub ingress: DirectionPlan,
+    pub egress: DirectionPlan,
+}
+
+impl ReconcilePlan {
+    pub fn is_empty(&self) -> bool {
+        self.ingress.is_empty() && self.egress.is_empty()
+    }
+}
+
+impl fmt::Display for ReconcilePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "security group {} at version {}",
+            self.security_group_id, self.version
+        )?;
+        for (name, plan) in [("ingress", &self.ingress), ("egress", &self.egress)] {
+            if plan.is_empty() {
+                writeln!(f, "  {name}: no changes")?;
+                continue;
+            }
+            writeln!(f, "  {name}:")?;
+            for policy in &plan.removed {
+                writeln!(f, "    - {}", PolicyDisplay(policy))?;
+            }
+            for policy in &plan.added {
+                writeln!(f, "    + {}", PolicyDisplay(policy))?;
+            }
+            if plan.reordered() {
+                writeln!(f, "    ~ reordered")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct PolicyDisplay<'a>(&'a SecurityGroupPolicy);
+
+impl fmt::Display for PolicyDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = self.0;
+        write!(f, "{}", p.action)?;
+        match (&p.service_template, p.protocol) {
+            (Some(t), _) => write!(
+                f,
+                " service {}",
+                t.service_id
+                    .as_deref()
+                    .or(t.service_group_id.as_deref())
+                    .unwrap_or_default()
+            )?,
+            (None, Some(protocol)) => {
+                write!(f, " {protocol} {}", p.port.as_deref().unwrap_or("ALL"))?
+            }
+            (None, None) => {}
+        }
+        let peer = p
+            .cidr_block
+            .as_deref()
+            .or(p.ipv6_cidr_block.as_deref())
+            .or(p.security_group_id.as_deref())
+            .or(p
+                .address_template
+                .as_ref()
+                .and_then(|t| t.address_id.as_deref().or(t.address_group_id.as_deref())));
+        if let Some(peer) = peer {
+            write!(f, " {peer}")?;
+        }
+        if let Some(description) = &p.policy_description {
+            write!(f, " # {description}")?;
+        }
+        Ok(())
+    }
+}
+
+impl SecurityGroupBuilder {
+    /// diff the current rules of the group against `desired`, keeping the order of `desired`.
+    ///
+    /// a direction without any desired rule is left untouched, see [`Self::plan_managed`].
+    pub async fn plan(
+        &self,
+        region: &Region,
+        group_id: &str,
+        desired: Vec<Policy>,
+    ) -> Result<ReconcilePlan> {
+        let managed: Vec<_> = desired.iter().map(|policy| policy.direction).collect();
+        self.plan_managed(region, group_id, desired, &managed).await
+    }
+
+    /// like [`Self::plan`], but every direction of `managed` is reconciled, so a managed
+    /// direction without desired rules gets all its rules removed.
+    pub async fn plan_managed(
+        &self,
+        region: &Region,
+        group_id: &str,
+        desired: Vec<Policy>,
+        managed: &[Direction],
+    ) -> Result<ReconcilePlan> {
+        let current = self
+            .describe_security_group_policies(region, group_id)
+            .await?;
+        Ok(plan(group_id, current, desired, managed))
+    }
+
+    /// set the desired rules of `plan` in one call carrying the version of the plan.
+    ///
+    /// that is ReplaceSecurityGroupPolicies when no direction changes its number of rules, and
+    /// ModifySecurityGroupPolicies with the full desired lists otherwise. fails with
+    /// [`TencentCloudError::PolicyVersionConflict`] when the api reports that the rules changed
+    /// since the plan was made, nothing is applied then.
+    pub async fn apply(&self, region: &Region, plan: &ReconcilePlan) -> Result<()> {
+        let result = match step(plan) {
+            None => return Ok(()),
+            Some(Step::Replace(request)) => {
+                self.replace_security_group_policies(region, &request).await
+            }
+            Some(Step::Modify(request)) => {
+                self.modify_security_group_policies(
+                    region,
+                    &request.security_group_id,
+                    request.security_group_policy_set,
+                )
+                .await
+            }
+        };
+        match result {
+            Err(e) if is_version_conflict(&e) => {
+                let actual = self
+                    .describe_security_group_policies(region, &plan.security_group_id)
+                    .await
+                    .ok()
+                    .and_then(|current| current.version)
+                    .unwrap_or_default();
+                Err(TencentCloudError::PolicyVersionConflict {
+                    security_group_id: plan.security_group_id.clone(),
+                    expected: plan.version.clone(),
+                    actual,
+                })
+            }
+            result => result,
+        }
+    }
+
+    /// [`Self::plan`] then [`Self::apply`], returns the applied plan.
+    pub async fn reconcile(
+        &self,
+        region: &Region,
+        group_id: &str,
+        desired: Vec<Policy>,
+    ) -> Result<ReconcilePlan> {
+        let plan = self.plan(region, group_id, desired).await?;
+        self.apply(region, &plan).await?;
+        Ok(plan)
+    }
+}
+
+fn plan(
+    group_id: &str,
+    current: SecurityGroupPolicySet,
+    desired: Vec<Policy>,
+    managed: &[Direction],
+) -> ReconcilePlan {
+    let list = |direction| managed.contains(&direction).then(Vec::new);
+    let (mut ingress, mut egress) = (list(Direction::Ingress), list(Direction::Egress));
+    for policy in desired {
+        let list = match policy.direction {
+            Direction::Ingress => &mut ingress,
+            Direction::Egress => &mut egress,
+        };
+        list.get_or_insert_with(Vec::new).push(policy.into());
+    }
+    ReconcilePlan {
+        security_group_id: group_id.to_owned(),
+        version: current.version.unwrap_or_default(),
+        ingress: DirectionPlan::new(current.ingress, ingress),
+        egress: DirectionPlan::new(current.egress, egress),
+    }
+}
+
+/// the single api call of [`SecurityGroupBuilder::apply`].
+#[derive(Debug)]
+enum Step {
+    Replace(ReplaceSecurityGroupPoliciesRequest),
+    Modify(ModifySecurityGroupPoliciesRequest),
+}
+
+/// the call turning the current rules into the desired ones, `None` if there is nothing to do.
+fn step(plan: &ReconcilePlan) -> Option<Step> {
+    if plan.is_empty() {
+        return None;
+    }
+    // an empty version would turn the call into an unchecked overwrite
+    let version = Some(plan.version.clone()).filter(|version| !version.is_empty());
+    let (ingress, egress) = (&plan.ingress, &plan.egress);
+    if ingress.current.len() == ingress.desired.len()
+        && egress.current.len() == egress.desired.len()
+    {
+        // rules are matched by content, so no `PolicyIndex` in either set
+        let changed = |rules: &DirectionPlan| -> (Vec<_>, Vec<_>) {
+            rules
+                .current
+                .iter()
+                .zip(&rules.desired)
+                .filter(|(current, desired)| current != desired)
+                .map(|(current, desired)| (current.clone(), desired.clone()))
+                .unzip()
+        };
+        let (ingress_original, ingress) = changed(ingress);
+        let (egress_original, egress) = changed(egress);
+        return Some(Step::Replace(ReplaceSecurityGroupPoliciesRequest {
+            security_group_id: plan.security_group_id.clone(),
+            security_group_policy_set: SecurityGroupPolicySet {
+                version,
+                ingress,
+                egress,
+            },
+            original: Some(SecurityGroupPolicySet {
+                version: None,
+                ingress: ingress_original,
+                egress: egress_original,
+            }),
+        }));
+    }
+    Some(Step::Modify(ModifySecurityGroupPoliciesRequest {
+        security_group_id: plan.security_group_id.clone(),
+        security_group_policy_set: SecurityGroupPolicySet {
+            version,
+            ingress: ingress.desired.clone(),
+            egress: egress.desired.clone(),
+        },
+        sort_policys: Some(true),
+    }))
+}
+
+/// the api rejected a `Version` that is not the current one of the group.
+fn is_version_conflict(e: &TencentCloudError) -> bool {
+    e.error_code()
+        == Some(ErrorCode::UnsupportedOperation(Some(
+            "VersionMismatch".into(),
+        )))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::client::Action;
+
+    #[test]
+    fn test_plan() {
+        let current: SecurityGroupPolicySet = serde_json::from_value(json!({
+            "Version": "7",
+            "Ingress": [
+                {"PolicyIndex": 0, "Protocol": "TCP", "Port": "22", "CidrBlock": "0.0.0.0/0",
+                    "Ipv6CidrBlock": "", "SecurityGroupId": "", "Action": "ACCEPT",
+                    "PolicyDescription": "", "ModifyTime": "2020-03-10 10:43:51",
+                    "ServiceTemplate": {"ServiceId": "", "ServiceGroupId": ""},
+                    "AddressTemplate": {"AddressId": "", "AddressGroupId": ""}},
+                {"PolicyIndex": 1, "Protocol": "TCP", "Port": "80,443", "CidrBlock": "0.0.0.0/0",
+                    "Action": "ACCEPT", "PolicyDescription": "web"}
+            ],
+            "Egress": [
+                {"PolicyIndex": 0, "Protocol": "ALL", "Port": "ALL", "CidrBlock": "0.0.0.0/0",
+                    "Action": "ACCEPT"}
+            ]
+        }))
+        .unwrap();
+        let desired: Vec<Policy> = serde_yaml::from_str(
+            "
+            - direction: ingress
+              protocol: TCP
+              port: 80,443
+              cidr_block: 0.0.0.0/0
+              action: ACCEPT
+              description: web
+            - direction: ingress
+              protocol: TCP
+              port: '22'
+              cidr_block: 10.0.0.0/8
+              action: ACCEPT
+            ",
+        )
+        .unwrap();
+
+        let plan = plan(
+            "sg-1",
+            current.clone(),
+            desired.clone(),
+            &[Direction::Ingress],
+        );
+        assert_eq!(plan.version, "7");
+        assert!(!plan.egress.managed && plan.egress.is_empty());
+        assert_eq!(plan.ingress.removed.len(), 1);
+        assert_eq!(plan.ingress.added.len(), 1);
+        assert_eq!(
+            plan.to_string(),
+            "security group sg-1 at version 7
+  ingress:
+    - ACCEPT TCP 22 0.0.0.0/0
+    + ACCEPT TCP 22 10.0.0.0/8
+  egress: no changes
+"
+        );
+
+        // the current rules, in another order
+        let web = desired[0].clone();
+        let ssh = Policy {
+            port: Some("22".into()),
+            cidr_block: Some("0.0.0.0/0".into()),
+            ..desired[1].clone()
+        };
+        let ingress = [Direction::Ingress];
+        let plan = super::plan(
+            "sg-1",
+            current.clone(),
+            vec![web.clone(), ssh.clone()],
+            &ingress,
+        );
+        assert!(plan.ingress.reordered());
+        assert!(super::plan("sg-1", current.clone(), vec![ssh, web], &ingress).is_empty());
+
+        // a managed direction without desired rules is emptied
+        let plan = super::plan("sg-1", current, vec![], &[Direction::Egress]);
+        assert!(plan.egress.managed && plan.egress.desired.is_empty());
+        assert_eq!(plan.egress.removed.len(), 1);
+        assert!(plan.ingress.is_empty());
+    }
+
+    fn body(plan: &ReconcilePlan) -> Option<serde_json::Value> {
+        fn body<A: Action>(request: &A) -> serde_json::Value {
+            json!({"Action": A::ACTION, "Body": request})
+        }
+        step(plan).map(|step| match step {
+            Step::Replace(request) => body(&request),
+            Step::Modify(request) => body(&request),
+        })
+    }
+
+    #[test]
+    fn test_step() {
+        let desired: Vec<Policy> = serde_yaml::from_str(
+            "
+            - direction: ingress
+              protocol: TCP
+              port: '22'
+              cidr_block: 10.0.0.0/8
+              action: ACCEPT
+            - direction: ingress
+              protocol: TCP
+              port: 80,443
+              cidr_block: 0.0.0.0/0
+              action: ACCEPT
+              description: web
+            - direction: egress
+              protocol: ALL
+              cidr_block: 0.0.0.0/0
+              action: ACCEPT
+            ",
+        )
+        .unwrap();
+        let ssh = json!({"Protocol": "TCP", "Port": "22", "CidrBlock": "10.0.0.0/8",
+            "Action": "ACCEPT"});
+        let web = json!({"Protocol": "TCP", "Port": "80,443", "CidrBlock": "0.0.0.0/0",
+            "Action": "ACCEPT", "PolicyDescription": "web"});
+        let all = json!({"Protocol": "ALL", "Port": "ALL", "CidrBlock": "0.0.0.0/0",
+            "Action": "ACCEPT"});
+
+        // first apply on a fresh group, still checked against its version
+        let fresh: SecurityGroupPolicySet =
+            serde_json::from_value(json!({"Version": "0", "Ingress": [], "Egress": []})).unwrap();
+        let managed = [Direction::Ingress, Direction::Egress];
+        let plan = plan("sg-1", fresh, desired.clone(), &managed);
+        assert_eq!(
+            body(&plan),
+            Some(json!({"Action": "ModifySecurityGroupPolicies", "Body": {
+                "SecurityGroupId": "sg-1",
+                "SecurityGroupPolicySet": {"Version": "0", "Ingress": [ssh, web], "Egress": [all]},
+                "SortPolicys": true,
+            }}))
+        );
+        assert!(super::plan("sg-1", plan_set(&plan), desired.clone(), &managed).is_empty());
+
+        // existing group: ssh changed in place, icmp removed, egress emptied
+        let current: SecurityGroupPolicySet = serde_json::from_value(json!({
+            "Version": "7",
+            "Ingress": [
+                {"PolicyIndex": 0, "Protocol": "TCP", "Port": "22", "CidrBlock": "0.0.0.0/0",
+                    "Action": "ACCEPT", "PolicyDescription": ""},
+                {"PolicyIndex": 1, "Protocol": "TCP", "Port": "80,443", "CidrBlock": "0.0.0.0/0",
+                    "Action": "ACCEPT", "PolicyDescription": "web"},
+                {"PolicyIndex": 2, "Protocol": "ICMP", "Port": "ALL", "CidrBlock": "0.0.0.0/0",
+                    "Action": "ACCEPT"}
+            ],
+            "Egress": [
+                {"PolicyIndex": 0, "Protocol": "ALL", "Port": "ALL", "CidrBlock": "0.0.0.0/0",
+                    "Action": "ACCEPT"}
+            ]
+        }))
+        .unwrap();
+        let plan = super::plan("sg-1", current.clone(), desired[..2].to_vec(), &managed);
+        assert_eq!(
+            body(&plan),
+            Some(json!({"Action": "ModifySecurityGroupPolicies", "Body": {
+                "SecurityGroupId": "sg-1",
+                "SecurityGroupPolicySet": {"Version": "7", "Ingress": [ssh, web]},
+                "SortPolicys": true,
+            }}))
+        );
+
+        // same number of rules: only the changed ones, matched by content
+        let icmp = Policy {
+            protocol: Some(Protocol::Icmp),
+            port: None,
+            description: None,
+            ..desired[1].clone()
+        };
+        let desired = vec![
+            desired[0].clone(),
+            desired[1].clone(),
+            icmp,
+            desired[2].clone(),
+        ];
+        let plan = super::plan("sg-1", current, desired, &managed);
+        assert_eq!(
+            body(&plan),
+            Some(json!({"Action": "ReplaceSecurityGroupPolicies", "Body": {
+                "SecurityGroupId": "sg-1",
+                "SecurityGroupPolicySet": {"Version": "7", "Ingress": [ssh]},
+                "OriginalSecurityGroupPolicySet": {"Ingress": [
+                    {"Protocol": "TCP", "Port": "22", "CidrBlock": "0.0.0.0/0", "Action": "ACCEPT"},
+                ]},
+            }}))
+        );
+    }
+
+    /// the rules of the group after applying `plan`
+    fn plan_set(plan: &ReconcilePlan) -> SecurityGroupPolicySet {
+        SecurityGroupPolicySet {
+            version: Some("1".into()),
+            ingress: plan.ingress.desired.clone(),
+            egress: plan.egress.desired.clone(),
+        }
+    }
+}
@@ -1,35 +1,63 @@
 use std::sync::Arc;
 
-use serde::Deserialize;
-use serde_json::json;
+use serde::{
+    de::{value::StrDeserializer, IntoDeserializer},
+    Deserialize, Deserializer, Serialize,
+};
+use strum::Display;
 
 use crate::{
-    client::{TencentCloudBaseClient, ACTION_HEADER, REGION_HEADER},
+    client::{impl_action, impl_paginate, Paginator, TencentCloudBaseClient},
     constant::Region,
+    error::Result,
 };
 
+use super::cvm_instance::{OperationResponse, Tag};
+
 pub struct SecurityGroupBuilder {
     client: Arc<TencentCloudBaseClient>,
-    service_name: String,
-    version: String,
 }
 
-const DESCRIBE_SECURITY_GROUPS: &str = "DescribeSecurityGroups";
+const SERVICE: &str = "vpc";
+const VERSION: &str = "2017-03-12";
 
-#[derive(Debug, Deserialize)]
+/// vpc takes `Offset`/`Limit` of DescribeSecurityGroups as strings
+fn serialize_string_number<S: serde::Serializer>(
+    value: &Option<u64>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// DescribeSecurityGroupsRequest
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct DescribeSecurityGroups {
-    pub response: DescribeSecurityGroupsInner,
+pub struct DescribeSecurityGroupsRequest {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_string_number"
+    )]
+    pub offset: Option<u64>,
+    /// page size, at most 100
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_string_number"
+    )]
+    pub limit: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct DescribeSecurityGroupsInner {
+pub struct DescribeSecurityGroups {
     pub total_count: usize,
     pub security_group_set: Vec<SecurityGroupInfo>,
+    pub request_id: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct SecurityGroupInfo {
     pub security_group_id: String,
@@ -38,29 +66,549 @@ pub struct SecurityGroupInfo {
     pub project_id: String,
     pub is_default: bool,
     pub created_time: String,
+    #[serde(default, deserialize_with = "super::null_default")]
+    pub tag_set: Vec<Tag>,
 }
 
-impl SecurityGroupBuilder {
-    pub fn new(client: Arc<TencentCloudBaseClient>) -> Self {
+/// CreateSecurityGroupRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CreateSecurityGroupRequest {
+    pub group_name: String,
+    pub group_description: String,
+    /// the default project if `None`
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_string_number"
+    )]
+    pub project_id: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CreateSecurityGroupResponse {
+    pub security_group: SecurityGroupInfo,
+    pub request_id: String,
+}
+
+/// DeleteSecurityGroupRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeleteSecurityGroupRequest {
+    pub security_group_id: String,
+}
+
+/// DescribeSecurityGroupPoliciesRequest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DescribeSecurityGroupPoliciesRequest {
+    pub security_group_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DescribeSecurityGroupPoliciesResponse {
+    pub security_group_policy_set: SecurityGroupPolicySet,
+    pub request_id: String,
+}
+
+/// Rules of a security group, `version` increases with every change of the rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SecurityGroupPolicySet {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub egress: Vec<SecurityGroupPolicy>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ingress: Vec<SecurityGroupPolicy>,
+}
+
+impl SecurityGroupPolicySet {
+    pub fn ingress(policies: Vec<SecurityGroupPolicy>) -> Self {
         Self {
-            client,
-            service_name: "vpc".into(),
-            version: "2017-03-12".into(),
+            ingress: policies,
+            ..Default::default()
         }
     }
+    pub fn egress(policies: Vec<SecurityGroupPolicy>) -> Self {
+        Self {
+            egress: policies,
+            ..Default::default()
+        }
+    }
+    /// only apply when the rules are still at `version`
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+}
+
+/// One ingress or egress rule.
+///
+/// The peer is one of `cidr_block`, `ipv6_cidr_block`, `security_group_id` or
+/// `address_template`, the ports either `protocol` + `port` or `service_template`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SecurityGroupPolicy {
+    /// position in its direction, from 0, set by the api
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_index: Option<i64>,
+    /// `None` for rules using a `service_template`, the api returns `""` for those
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_protocol"
+    )]
+    pub protocol: Option<Protocol>,
+    /// `ALL`, `80`, `8000-8010` or `80,443`
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_empty"
+    )]
+    pub port: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_default"
+    )]
+    pub service_template: Option<ServiceTemplateSpecification>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_empty"
+    )]
+    pub cidr_block: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_empty"
+    )]
+    pub ipv6_cidr_block: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_empty"
+    )]
+    pub security_group_id: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_default"
+    )]
+    pub address_template: Option<AddressTemplateSpecification>,
+    pub action: PolicyAction,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_empty"
+    )]
+    pub policy_description: Option<String>,
+    /// set by the api
+    #[serde(default, skip_serializing, deserialize_with = "non_empty")]
+    pub modify_time: Option<String>,
+}
+
+impl SecurityGroupPolicy {
+    fn new(action: PolicyAction, protocol: Protocol) -> Self {
+        Self {
+            policy_index: None,
+            protocol: Some(protocol),
+            port: None,
+            service_template: None,
+            cidr_block: None,
+            ipv6_cidr_block: None,
+            security_group_id: None,
+            address_template: None,
+            action,
+            policy_description: None,
+            modify_time: None,
+        }
+    }
+    pub fn accept(protocol: Protocol) -> Self {
+        Self::new(PolicyAction::Accept, protocol)
+    }
+    pub fn drop(protocol: Protocol) -> Self {
+        Self::new(PolicyAction::Drop, protocol)
+    }
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port.to_string());
+        self
+    }
+    /// inclusive
+    pub fn port_range(mut self, from: u16, to: u16) -> Self {
+        self.port = Some(format!("{from}-{to}"));
+        self
+    }
+    pub fn ports(mut self, ports: &[u16]) -> Self {
+        let ports: Vec<_> = ports.iter().map(u16::to_string).collect();
+        self.port = Some(ports.join(","));
+        self
+    }
+    pub fn cidr_block(mut self, cidr: impl Into<String>) -> Self {
+        self.cidr_block = Some(cidr.into());
+        self
+    }
+    pub fn ipv6_cidr_block(mut self, cidr: impl Into<String>) -> Self {
+        self.ipv6_cidr_block = Some(cidr.into());
+        self
+    }
+    /// traffic from/to the instances of another security group
+    pub fn security_group_id(mut self, id: impl Into<String>) -> Self {
+        self.security_group_id = Some(id.into());
+        self
+    }
+    pub fn address_template(mut self, template: AddressTemplateSpecification) -> Self {
+        self.address_template = Some(template);
+        self
+    }
+    /// ports from a service template instead of `protocol` and `port`
+    pub fn service_template(mut self, template: ServiceTemplateSpecification) -> Self {
+        self.protocol = None;
+        self.port = None;
+        self.service_template = Some(template);
+        self
+    }
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.policy_description = Some(description.into());
+        self
+    }
+}
+
+fn deserialize_protocol<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Protocol>, D::Error> {
+    match Option::<String>::deserialize(deserializer)?
+        .as_deref()
+        .map(str::trim)
+    {
+        None | Some("") => Ok(None),
+        Some(protocol) => {
+            let protocol: StrDeserializer<'_, D::Error> = protocol.into_deserializer();
+            Protocol::deserialize(protocol).map(Some)
+        }
+    }
+}
+
+/// `""` as `None`, the api fills unset fields of a rule with empty strings.
+fn non_empty<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<String>, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.filter(|value| !value.trim().is_empty()))
+}
+
+/// an all empty template such as `{"ServiceId": "", "ServiceGroupId": ""}` as `None`.
+fn non_default<'de, D, T>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default + PartialEq,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.filter(|value| *value != T::default()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+pub enum Protocol {
+    #[serde(rename = "TCP", alias = "tcp")]
+    #[strum(serialize = "TCP")]
+    Tcp,
+    #[serde(rename = "UDP", alias = "udp")]
+    #[strum(serialize = "UDP")]
+    Udp,
+    #[serde(rename = "ICMP", alias = "icmp")]
+    #[strum(serialize = "ICMP")]
+    Icmp,
+    #[serde(rename = "ICMPv6", alias = "icmpv6", alias = "ICMPV6")]
+    #[strum(serialize = "ICMPv6")]
+    Icmpv6,
+    #[serde(rename = "GRE", alias = "gre")]
+    #[strum(serialize = "GRE")]
+    Gre,
+    #[serde(rename = "ALL", alias = "all")]
+    #[strum(serialize = "ALL")]
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[serde(rename_all = "UPPERCASE")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum PolicyAction {
+    #[serde(alias = "accept")]
+    Accept,
+    #[serde(alias = "drop")]
+    Drop,
+}
+
+/// `address_id` (ipm-) or `address_group_id` (ipmg-)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AddressTemplateSpecification {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_empty"
+    )]
+    pub address_id: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_empty"
+    )]
+    pub address_group_id: Option<String>,
+}
+
+/// `service_id` (ppm-) or `service_group_id` (ppmg-)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ServiceTemplateSpecification {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_empty"
+    )]
+    pub service_id: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "non_empty"
+    )]
+    pub service_group_id: Option<String>,
+}
+
+/// CreateSecurityGroupPolicies, DeleteSecurityGroupPolicies and ModifySecurityGroupPolicies
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SecurityGroupPoliciesRequest {
+    pub security_group_id: String,
+    pub security_group_policy_set: SecurityGroupPolicySet,
+}
+
+/// CreateSecurityGroupPoliciesRequest, adds rules of one direction, at the `policy_index` if set
+#[derive(Debug, Clone, Serialize)]
+#[serde(transparent)]
+pub struct CreateSecurityGroupPoliciesRequest(pub SecurityGroupPoliciesRequest);
+
+/// DeleteSecurityGroupPoliciesRequest, by `policy_index` or by full rule content
+#[derive(Debug, Clone, Serialize)]
+#[serde(transparent)]
+pub struct DeleteSecurityGroupPoliciesRequest(pub SecurityGroupPoliciesRequest);
+
+/// ModifySecurityGroupPoliciesRequest, resets all rules of the group at once
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ModifySecurityGroupPoliciesRequest {
+    pub security_group_id: String,
+    pub security_group_policy_set: SecurityGroupPolicySet,
+    /// keep the order of the given rules
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_policys: Option<bool>,
+}
+
+/// ReplaceSecurityGroupPoliciesRequest, replaces each rule of `original` with the rule at the
+/// same position in `security_group_policy_set`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReplaceSecurityGroupPoliciesRequest {
+    pub security_group_id: String,
+    pub security_group_policy_set: SecurityGroupPolicySet,
+    #[serde(
+        rename = "OriginalSecurityGroupPolicySet",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub original: Option<SecurityGroupPolicySet>,
+}
+
+impl_action!(SERVICE, VERSION, "DescribeSecurityGroups", DescribeSecurityGroupsRequest => DescribeSecurityGroups);
+impl_action!(SERVICE, VERSION, "CreateSecurityGroup", CreateSecurityGroupRequest => CreateSecurityGroupResponse);
+impl_action!(SERVICE, VERSION, "DeleteSecurityGroup", DeleteSecurityGroupRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "DescribeSecurityGroupPolicies", DescribeSecurityGroupPoliciesRequest => DescribeSecurityGroupPoliciesResponse);
+impl_action!(SERVICE, VERSION, "CreateSecurityGroupPolicies", CreateSecurityGroupPoliciesRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "DeleteSecurityGroupPolicies", DeleteSecurityGroupPoliciesRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "ModifySecurityGroupPolicies", ModifySecurityGroupPoliciesRequest => OperationResponse);
+impl_action!(SERVICE, VERSION, "ReplaceSecurityGroupPolicies", ReplaceSecurityGroupPoliciesRequest => OperationResponse);
+impl_paginate!(DescribeSecurityGroupsRequest, security_group_set: SecurityGroupInfo);
+
+impl SecurityGroupBuilder {
+    pub fn new(client: Arc<TencentCloudBaseClient>) -> Self {
+        Self { client }
+    }
+    /// all security groups of the region, walking every page
     pub async fn describe_security_groups(
         &self,
         region: &Region,
-    ) -> anyhow::Result<Vec<SecurityGroupInfo>> {
-        let resp = self
-            .client
-            .post(&self.service_name, &self.version)
-            .header(ACTION_HEADER, DESCRIBE_SECURITY_GROUPS)
-            .header(REGION_HEADER, region.to_string())
-            .json(&json!({}))
-            .send()
-            .await?;
-        let body: DescribeSecurityGroups = resp.json().await?;
-        Ok(body.response.security_group_set)
+    ) -> Result<Vec<SecurityGroupInfo>> {
+        self.describe_security_groups_pages(region, DescribeSecurityGroupsRequest::default())
+            .collect_all()
+            .await
+    }
+
+    pub fn describe_security_groups_pages(
+        &self,
+        region: &Region,
+        request: DescribeSecurityGroupsRequest,
+    ) -> Paginator<DescribeSecurityGroupsRequest> {
+        Paginator::new(self.client.clone(), region.clone(), request)
+    }
+
+    pub async fn create_security_group(
+        &self,
+        region: &Region,
+        request: &CreateSecurityGroupRequest,
+    ) -> Result<SecurityGroupInfo> {
+        let body = self.client.execute(region, request).await?;
+        Ok(body.security_group)
+    }
+
+    pub async fn delete_security_group(&self, region: &Region, group_id: &str) -> Result<()> {
+        let request = DeleteSecurityGroupRequest {
+            security_group_id: group_id.into(),
+        };
+        self.client.execute(region, &request).await?;
+        Ok(())
+    }
+
+    /// all rules of the group with their current `version`
+    pub async fn describe_security_group_policies(
+        &self,
+        region: &Region,
+        group_id: &str,
+    ) -> Result<SecurityGroupPolicySet> {
+        let request = DescribeSecurityGroupPoliciesRequest {
+            security_group_id: group_id.into(),
+        };
+        let body = self.client.execute(region, &request).await?;
+        Ok(body.security_group_policy_set)
+    }
+
+    /// add rules, only one of `ingress`/`egress` may be set per call
+    pub async fn create_security_group_policies(
+        &self,
+        region: &Region,
+        group_id: &str,
+        policies: SecurityGroupPolicySet,
+    ) -> Result<()> {
+        let request = CreateSecurityGroupPoliciesRequest(SecurityGroupPoliciesRequest {
+            security_group_id: group_id.into(),
+            security_group_policy_set: policies,
+        });
+        self.client.execute(region, &request).await?;
+        Ok(())
+    }
+
+    pub async fn delete_security_group_policies(
+        &self,
+        region: &Region,
+        group_id: &str,
+        policies: SecurityGroupPolicySet,
+    ) -> Result<()> {
+        let request = DeleteSecurityGroupPoliciesRequest(SecurityGroupPoliciesRequest {
+            security_group_id: group_id.into(),
+            security_group_policy_set: policies,
+        });
+        self.client.execute(region, &request).await?;
+        Ok(())
+    }
+
+    /// reset all rules of the group, fails if `policies.version` is set and outdated
+    pub async fn modify_security_group_policies(
+        &self,
+        region: &Region,
+        group_id: &str,
+        policies: SecurityGroupPolicySet,
+    ) -> Result<()> {
+        let request = ModifySecurityGroupPoliciesRequest {
+            security_group_id: group_id.into(),
+            security_group_policy_set: policies,
+            sort_policys: Some(true),
+        };
+        self.client.execute(region, &request).await?;
+        Ok(())
+    }
+
+    pub async fn replace_security_group_policies(
+        &self,
+        region: &Region,
+        request: &ReplaceSecurityGroupPoliciesRequest,
+    ) -> Result<()> {
+        self.client.execute(region, request).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_security_group_policies() {
+        let set = SecurityGroupPolicySet::ingress(vec![
+            SecurityGroupPolicy::accept(Protocol::Tcp)
+                .ports(&[80, 443])
+                .cidr_block("0.0.0.0/0")
+                .description("web"),
+            SecurityGroupPolicy::accept(Protocol::All).security_group_id("sg-1"),
+            SecurityGroupPolicy::drop(Protocol::Icmpv6).ipv6_cidr_block("::/0"),
+        ])
+        .with_version("3");
+        assert_eq!(
+            serde_json::to_value(&set).unwrap(),
+            json!({
+                "Version": "3",
+                "Ingress": [
+                    {"Protocol": "TCP", "Port": "80,443", "CidrBlock": "0.0.0.0/0",
+                        "Action": "ACCEPT", "PolicyDescription": "web"},
+                    {"Protocol": "ALL", "SecurityGroupId": "sg-1", "Action": "ACCEPT"},
+                    {"Protocol": "ICMPv6", "Ipv6CidrBlock": "::/0", "Action": "DROP"},
+                ]
+            })
+        );
+
+        let body: DescribeSecurityGroupPoliciesResponse = serde_json::from_value(json!({
+            "SecurityGroupPolicySet": {
+                "Version": "3",
+                "Egress": [],
+                "Ingress": [{"PolicyIndex": 0, "Protocol": "tcp", "Port": "22",
+                    "ServiceTemplate": {"ServiceId": "", "ServiceGroupId": ""},
+                    "CidrBlock": "10.0.0.0/8", "Ipv6CidrBlock": "", "SecurityGroupId": "",
+                    "AddressTemplate": {"AddressId": "", "AddressGroupId": ""},
+                    "Action": "accept", "PolicyDescription": "", "ModifyTime": "2020-03-10 10:43:51"}]
+            },
+            "RequestId": "r"
+        }))
+        .unwrap();
+        let policy = &body.security_group_policy_set.ingress[0];
+        assert_eq!(policy.protocol, Some(Protocol::Tcp));
+        assert_eq!(policy.action, PolicyAction::Accept);
+        // unset fields come back as "", a described rule can be sent back as is
+        assert_eq!(
+            serde_json::to_value(policy).unwrap(),
+            json!({"PolicyIndex": 0, "Protocol": "TCP", "Port": "22", "CidrBlock": "10.0.0.0/8",
+                "Action": "ACCEPT"})
+        );
+
+        // rules using a service template have no protocol
+        let policy: SecurityGroupPolicy = serde_json::from_value(json!({
+            "PolicyIndex": 1, "Protocol": "", "Port": "",
+            "ServiceTemplate": {"ServiceId": "ppm-1", "ServiceGroupId": ""},
+            "CidrBlock": "10.0.0.0/8", "Action": "ACCEPT"
+        }))
+        .unwrap();
+        assert!(policy.protocol.is_none());
+        assert_eq!(
+            policy.service_template.unwrap().service_id.as_deref(),
+            Some("ppm-1")
+        );
+
+        let group: SecurityGroupInfo = serde_json::from_value(json!({
+            "SecurityGroupId": "sg-1", "SecurityGroupName": "web", "SecurityGroupDesc": "",
+            "ProjectId": "0", "IsDefault": false, "CreatedTime": "2020-03-10 10:43:51",
+            "TagSet": null
+        }))
+        .unwrap();
+        assert!(group.tag_set.is_empty());
     }
 }
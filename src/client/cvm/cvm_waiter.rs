@@ -0,0 +1,312 @@
+use std::time::Duration;
+
+use tokio::time::{sleep, Instant};
+use tracing::debug;
+
+use crate::{
+    constant::Region,
+    error::{Result, TencentCloudError},
+};
+
+use super::cvm_instance::{CVMInstanceBuilder, Instance, InstanceState, LatestOperationState};
+
+/// Polling settings of the instance waiters.
+///
+/// The delay before poll `n` (starting at 0) is
+/// `min(initial_interval * multiplier^n, max_interval)`.
+#[derive(Debug, Clone)]
+pub struct WaitPolicy {
+    pub initial_interval: Duration,
+    pub max_interval: Duration,
+    pub multiplier: f64,
+    pub timeout: Duration,
+}
+
+impl Default for WaitPolicy {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_secs(2),
+            max_interval: Duration::from_secs(15),
+            multiplier: 1.5,
+            timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+impl WaitPolicy {
+    fn interval(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(attempt.min(64) as i32);
+        self.initial_interval
+            .mul_f64(factor.min(u32::MAX as f64))
+            .min(self.max_interval)
+    }
+}
+
+impl CVMInstanceBuilder {
+    /// poll the instance until `predicate` holds, `None` is an instance that is not listed (yet or
+    /// anymore).
+    ///
+    /// fails fast once the latest operation of the instance is `operation`, e.g.
+    /// `StopInstances`, and `FAILED`. a failed latest operation of another action is ignored.
+    pub async fn wait_for<F>(
+        &self,
+        region: &Region,
+        instance_id: &str,
+        policy: &WaitPolicy,
+        operation: Option<&str>,
+        predicate: F,
+    ) -> Result<Option<Instance>>
+    where
+        F: FnMut(Option<&Instance>) -> bool,
+    {
+        let fail_on = FailOn {
+            launch_failed: false,
+            operation,
+        };
+        self.poll(region, instance_id, policy, fail_on, predicate)
+            .await
+    }
+
+    async fn poll<F>(
+        &self,
+        region: &Region,
+        instance_id: &str,
+        policy: &WaitPolicy,
+        fail_on: FailOn<'_>,
+        mut predicate: F,
+    ) -> Result<Option<Instance>>
+    where
+        F: FnMut(Option<&Instance>) -> bool,
+    {
+        let start = Instant::now();
+        for attempt in 0.. {
+            let instance = self.describe_instance_by_id(region, instance_id).await?;
+            let done = predicate(instance.as_ref());
+            if check(instance.as_ref(), done, fail_on)? {
+                return Ok(instance);
+            }
+            if let Some(instance) = &instance {
+                debug!(
+                    "waiting for {instance_id}: {} {:?}",
+                    instance.instance_state, instance.latest_operation_state
+                );
+            }
+            let delay = policy.interval(attempt);
+            if start.elapsed() + delay > policy.timeout {
+                break;
+            }
+            sleep(delay).await;
+        }
+        Err(TencentCloudError::WaitTimeout {
+            instance_id: instance_id.to_owned(),
+            elapsed: start.elapsed(),
+        })
+    }
+
+    /// `RUNNING`, with a public ip unless the instance has no public bandwidth.
+    ///
+    /// fails fast on `LAUNCH_FAILED`.
+    pub async fn wait_until_running(
+        &self,
+        region: &Region,
+        instance_id: &str,
+        policy: &WaitPolicy,
+    ) -> Result<Instance> {
+        let instance = self
+            .poll(region, instance_id, policy, RUNNING, is_running)
+            .await?;
+        Ok(instance.expect("predicate requires an instance"))
+    }
+
+    /// `STOPPED`, fails fast when StopInstances failed.
+    pub async fn wait_until_stopped(
+        &self,
+        region: &Region,
+        instance_id: &str,
+        policy: &WaitPolicy,
+    ) -> Result<Instance> {
+        let instance = self
+            .poll(region, instance_id, policy, STOPPED, is_stopped)
+            .await?;
+        Ok(instance.expect("predicate requires an instance"))
+    }
+
+    /// gone from DescribeInstances, or `SHUTDOWN` for isolated prepaid instances.
+    ///
+    /// fails fast when TerminateInstances failed.
+    pub async fn wait_until_terminated(
+        &self,
+        region: &Region,
+        instance_id: &str,
+        policy: &WaitPolicy,
+    ) -> Result<()> {
+        self.poll(region, instance_id, policy, TERMINATED, is_terminated)
+            .await?;
+        Ok(())
+    }
+}
+
+/// what a waiter fails fast on, besides timing out.
+#[derive(Debug, Clone, Copy)]
+struct FailOn<'a> {
+    launch_failed: bool,
+    /// action whose `FAILED` latest operation ends the wait
+    operation: Option<&'a str>,
+}
+
+const RUNNING: FailOn = FailOn {
+    launch_failed: true,
+    operation: None,
+};
+const STOPPED: FailOn = FailOn {
+    launch_failed: false,
+    operation: Some("StopInstances"),
+};
+const TERMINATED: FailOn = FailOn {
+    launch_failed: false,
+    operation: Some("TerminateInstances"),
+};
+
+fn is_running(instance: Option<&Instance>) -> bool {
+    instance.is_some_and(|i| {
+        let has_ip = i
+            .public_ip_addresses
+            .as_ref()
+            .is_some_and(|ips| !ips.is_empty());
+        let wants_ip = i
+            .internet_accessible
+            .as_ref()
+            .is_some_and(|net| net.internet_max_bandwidth_out > 0);
+        i.instance_state == InstanceState::RUNNING && (has_ip || !wants_ip)
+    })
+}
+
+fn is_stopped(instance: Option<&Instance>) -> bool {
+    instance.is_some_and(|i| i.instance_state == InstanceState::STOPPED)
+}
+
+fn is_terminated(instance: Option<&Instance>) -> bool {
+    instance.is_none_or(|i| i.instance_state == InstanceState::SHUTDOWN)
+}
+
+/// whether one poll ends the wait, `done` being the result of the predicate, which wins over
+/// any failure.
+fn check(instance: Option<&Instance>, done: bool, fail_on: FailOn) -> Result<bool> {
+    let Some(instance) = instance.filter(|_| !done) else {
+        return Ok(done);
+    };
+    let failed = |reason: String| TencentCloudError::InstanceFailed {
+        instance_id: instance.instance_id.clone(),
+        reason,
+    };
+    if fail_on.launch_failed && instance.instance_state == InstanceState::LAUNCH_FAILED {
+        return Err(failed(
+            instance
+                .latest_operation_error_msg
+                .clone()
+                .unwrap_or_else(|| "LAUNCH_FAILED".into()),
+        ));
+    }
+    let operation = instance.latest_operation.as_deref();
+    if fail_on.operation.is_some()
+        && operation == fail_on.operation
+        && instance.latest_operation_state == Some(LatestOperationState::Failed)
+    {
+        return Err(failed(format!(
+            "{} failed: {}",
+            operation.unwrap_or_default(),
+            instance
+                .latest_operation_error_msg
+                .as_deref()
+                .unwrap_or_default()
+        )));
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let policy = WaitPolicy {
+            initial_interval: Duration::from_secs(2),
+            max_interval: Duration::from_secs(10),
+            multiplier: 2.0,
+            timeout: Duration::from_secs(60),
+        };
+        for (attempt, secs) in [(0, 2), (1, 4), (2, 8), (3, 10), (100, 10)] {
+            assert_eq!(policy.interval(attempt), Duration::from_secs(secs));
+        }
+    }
+
+    fn instance(state: &str, operation: &str, operation_state: &str) -> Instance {
+        serde_json::from_value(serde_json::json!({
+            "InstanceId": "ins-1",
+            "InstanceName": "web",
+            "InstanceState": state,
+            "InstanceType": "S5.MEDIUM4",
+            "CPU": 2,
+            "Memory": 4,
+            "Placement": {"Zone": "ap-guangzhou-3"},
+            "InstanceChargeType": "POSTPAID_BY_HOUR",
+            "ImageId": "img-1",
+            "OsName": "Ubuntu Server 22.04 LTS 64bit",
+            "SystemDisk": {"DiskSize": 50},
+            "CreatedTime": "2020-03-10T02:43:51Z",
+            "LatestOperation": operation,
+            "LatestOperationState": operation_state,
+            "LatestOperationErrorMsg": "ResourceInsufficient",
+            "Uuid": "e85f1388-0422-410d-8e50-bef540e78c18",
+        }))
+        .unwrap()
+    }
+
+    fn assert_failed(result: Result<bool>) {
+        match result {
+            Err(TencentCloudError::InstanceFailed {
+                instance_id,
+                reason,
+            }) => {
+                assert_eq!(instance_id, "ins-1");
+                assert!(reason.contains("ResourceInsufficient"), "{reason}");
+            }
+            other => panic!("expected InstanceFailed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let pending = instance("PENDING", "RunInstances", "OPERATING");
+        assert!(!check(Some(&pending), is_running(Some(&pending)), RUNNING).unwrap());
+
+        // fails on the first poll, also when already failed before waiting
+        let launch_failed = instance("LAUNCH_FAILED", "RunInstances", "FAILED");
+        assert_failed(check(
+            Some(&launch_failed),
+            is_running(Some(&launch_failed)),
+            RUNNING,
+        ));
+        let stop_failed = instance("RUNNING", "StopInstances", "FAILED");
+        assert_failed(check(
+            Some(&stop_failed),
+            is_stopped(Some(&stop_failed)),
+            STOPPED,
+        ));
+
+        // a stale failed operation of another action does not matter
+        let running = instance("RUNNING", "ResetInstancesPassword", "FAILED");
+        assert!(check(Some(&running), is_running(Some(&running)), RUNNING).unwrap());
+        assert!(!check(Some(&running), is_stopped(Some(&running)), STOPPED).unwrap());
+
+        // cleaning up a failed launch waits until the instance is gone
+        assert!(!check(
+            Some(&launch_failed),
+            is_terminated(Some(&launch_failed)),
+            TERMINATED
+        )
+        .unwrap());
+        assert!(check(None, is_terminated(None), TERMINATED).unwrap());
+    }
+}
@@ -1,67 +1,147 @@
-use reqwest::StatusCode;
-use serde::Deserialize;
-use serde_json::json;
+use std::str::FromStr;
 
-use crate::{
-    client::constant::{ACTION_HEADER, REGION_HEADER},
-    constant::Region,
-};
+use serde::{Deserialize, Serialize};
 
-const DESCRIBE_ZONES: &str = "DescribeZones";
+use crate::{client::impl_action, constant::Region, error::Result};
 
 use super::*;
 pub struct CVMZoneBuilder {
     client: Arc<TencentCloudBaseClient>,
-    service_name: String,
-    version: String,
 }
 
-#[derive(Debug, Deserialize)]
+/// DescribeZonesRequest
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "PascalCase")]
-struct DescribeZoneResponse {
-    pub response: DescribeZoneResponseInner,
-}
+pub struct DescribeZonesRequest {}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-struct DescribeZoneResponseInner {
-    #[allow(unused)]
+pub struct DescribeZoneResponse {
     pub total_count: usize,
     pub zone_set: Vec<ZoneInfo>,
+    pub request_id: String,
 }
-#[derive(Debug, Deserialize)]
+
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-struct ZoneInfo {
+pub struct ZoneInfo {
+    /// e.g. `ap-guangzhou-3`
     pub zone: String,
+    /// e.g. `广州三区`, in the language of the client
+    pub zone_name: String,
+    /// e.g. `100003`
+    pub zone_id: String,
+    pub zone_state: ZoneState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ZoneState {
+    Available,
+    Unavailable,
+    #[serde(other)]
+    Unknown,
+}
+
+/// DescribeRegionsRequest
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DescribeRegionsRequest {}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DescribeRegionsResponse {
+    pub total_count: usize,
+    pub region_set: Vec<RegionInfo>,
+    pub request_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RegionInfo {
+    /// e.g. `ap-guangzhou`
+    pub region: String,
+    /// e.g. `华南地区(广州)`, in the language of the client
+    pub region_name: String,
+    /// `AVAILABLE` or `UNAVAILABLE`
+    pub region_state: String,
 }
+
+impl RegionInfo {
+    /// the [`Region`] of this region, `None` for regions newer than the enum.
+    pub fn to_region(&self) -> Option<Region> {
+        Region::from_str(&self.region).ok()
+    }
+}
+
+impl_action!(SERVICE, VERSION, "DescribeZones", DescribeZonesRequest => DescribeZoneResponse);
+impl_action!(SERVICE, VERSION, "DescribeRegions", DescribeRegionsRequest => DescribeRegionsResponse);
+
 impl CVMZoneBuilder {
     pub fn new(client: Arc<TencentCloudBaseClient>) -> Self {
-        Self {
-            client,
-            service_name: "cvm".into(),
-            version: "2017-03-12".into(),
-        }
+        Self { client }
     }
-    pub async fn describe_zone(&self, region: &Region) -> anyhow::Result<Option<Vec<String>>> {
-        let resp = self
+    /// all zones of the region, available or not
+    pub async fn describe_zone(&self, region: &Region) -> Result<Vec<ZoneInfo>> {
+        let body = self
             .client
-            .post(&self.service_name, &self.version)
-            .header(ACTION_HEADER, DESCRIBE_ZONES)
-            .header(REGION_HEADER, region.to_string())
-            .json(&json!({}))
-            .send()
+            .execute(region, &DescribeZonesRequest::default())
             .await?;
-        match resp.status() {
-            StatusCode::OK => {
-                let body: DescribeZoneResponse = resp.json().await?;
-                // println!("DescribeZoneResponse body: {body:?}");
-                Ok(Some(
-                    body.response.zone_set.into_iter().map(|z| z.zone).collect(),
-                ))
-            }
-            rest => Err(anyhow::anyhow!(
-                "err get code {rest}, msg {}",
-                resp.text().await?
-            )),
-        }
+        Ok(body.zone_set)
+    }
+
+    /// zones of the region currently accepting new instances
+    pub async fn available_zones(&self, region: &Region) -> Result<Vec<ZoneInfo>> {
+        let mut zones = self.describe_zone(region).await?;
+        zones.retain(|zone| zone.zone_state == ZoneState::Available);
+        Ok(zones)
+    }
+
+    /// regions where cvm is offered, independent of the client region
+    pub async fn describe_regions(&self) -> Result<Vec<RegionInfo>> {
+        let body = self
+            .client
+            .execute_global(&DescribeRegionsRequest::default())
+            .await?;
+        Ok(body.region_set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_zones_and_regions() {
+        let body: DescribeZoneResponse = serde_json::from_value(json!({
+            "TotalCount": 2,
+            "ZoneSet": [
+                {"Zone": "ap-guangzhou-3", "ZoneName": "广州三区", "ZoneId": "100003",
+                    "ZoneState": "AVAILABLE"},
+                {"Zone": "ap-guangzhou-1", "ZoneName": "广州一区", "ZoneId": "100001",
+                    "ZoneState": "UNAVAILABLE"}
+            ],
+            "RequestId": "r"
+        }))
+        .unwrap();
+        assert_eq!(body.zone_set[0].zone_state, ZoneState::Available);
+        assert_eq!(body.zone_set[1].zone_state, ZoneState::Unavailable);
+
+        let body: DescribeRegionsResponse = serde_json::from_value(json!({
+            "TotalCount": 2,
+            "RegionSet": [
+                {"Region": "ap-guangzhou", "RegionName": "华南地区(广州)", "RegionState": "AVAILABLE"},
+                {"Region": "ap-nowhere", "RegionName": "?", "RegionState": "AVAILABLE"}
+            ],
+            "RequestId": "r"
+        }))
+        .unwrap();
+        assert!(matches!(
+            body.region_set[0].to_region(),
+            Some(Region::Guangzhou)
+        ));
+        assert!(body.region_set[1].to_region().is_none());
     }
 }
@@ -1,12 +1,57 @@
 use std::sync::Arc;
 
+use serde::{Deserialize, Deserializer, Serialize};
+
 use super::TencentCloudBaseClient;
 
 pub mod cvm_instance;
 pub mod cvm_key;
+pub mod cvm_reconcile;
 pub mod cvm_security_group;
+pub mod cvm_waiter;
 pub mod cvm_zone;
 
+const SERVICE: &str = "cvm";
+const VERSION: &str = "2017-03-12";
+
+/// `Filters` item of the `Describe*` actions, values of one filter are OR-ed, filters are AND-ed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Filter {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+impl Filter {
+    pub fn new(name: impl Into<String>, values: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            values,
+        }
+    }
+}
+
+/// add `value` to the filter named `name`, creating it if needed.
+fn push_filter(filters: &mut Vec<Filter>, name: String, value: String) {
+    match filters.iter_mut().find(|f| f.name == name) {
+        Some(filter) => filter.values.push(value),
+        None => filters.push(Filter::new(name, vec![value])),
+    }
+}
+
+/// `null` as `T::default()`, the api returns `null` instead of `[]` for many empty lists.
+fn null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+fn ids(ids: impl IntoIterator<Item = impl Into<String>>) -> Vec<String> {
+    ids.into_iter().map(Into::into).collect()
+}
+
 pub struct CVMBuilder {
     client: Arc<TencentCloudBaseClient>,
 }
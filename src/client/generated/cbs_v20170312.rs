@@ -0,0 +1,368 @@
+// @generated by `cargo xtask codegen`, do not edit.
+// source: cbs/v20170312/api.json
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{impl_action, TencentCloudBaseClient},
+    constant::Region,
+};
+
+pub const SERVICE: &str = "cbs";
+pub const VERSION: &str = "2017-03-12";
+
+pub const ATTACH_DISKS: &str = "AttachDisks";
+pub const CREATE_DISKS: &str = "CreateDisks";
+pub const DESCRIBE_DISKS: &str = "DescribeDisks";
+pub const DETACH_DISKS: &str = "DetachDisks";
+pub const TERMINATE_DISKS: &str = "TerminateDisks";
+
+/// AttachDisks请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttachDisksRequest {
+    /// 将要被挂载的弹性云盘ID。通过[DescribeDisks](/document/product/362/16315)接口查询。单次最多可挂载10块弹性云盘。
+    #[serde(rename = "DiskIds")]
+    #[serde(default)]
+    pub disk_ids: Vec<String>,
+    /// 云服务器实例ID。
+    #[serde(rename = "InstanceId")]
+    #[serde(default)]
+    pub instance_id: String,
+    /// 可选参数，不传该参数则仅执行挂载操作。
+    #[serde(rename = "DeleteWithInstance")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_with_instance: Option<bool>,
+}
+
+/// AttachDisks返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttachDisksResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// CreateDisks请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateDisksRequest {
+    /// 实例所在的位置。
+    #[serde(rename = "Placement")]
+    #[serde(default)]
+    pub placement: Placement,
+    /// 云硬盘计费类型。
+    #[serde(rename = "DiskChargeType")]
+    #[serde(default)]
+    pub disk_charge_type: String,
+    /// 硬盘介质类型。
+    #[serde(rename = "DiskType")]
+    #[serde(default)]
+    pub disk_type: String,
+    /// 云盘显示名称。
+    #[serde(rename = "DiskName")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_name: Option<String>,
+    /// 云盘绑定的标签。
+    #[serde(rename = "Tags")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+    /// 预付费模式，即包年包月相关参数设置。
+    #[serde(rename = "DiskChargePrepaid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_charge_prepaid: Option<DiskChargePrepaid>,
+    /// 创建云硬盘数量，不传则默认为1。单次请求最多可创建的云盘数有限制。
+    #[serde(rename = "DiskCount")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_count: Option<u64>,
+    /// 云硬盘大小，单位为GB。
+    #[serde(rename = "DiskSize")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_size: Option<u64>,
+    /// 传入该参数用于创建加密云盘，取值固定为ENCRYPT。
+    #[serde(rename = "Encrypt")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypt: Option<String>,
+    /// 用于保证请求幂等性的字符串。
+    #[serde(rename = "ClientToken")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_token: Option<String>,
+}
+
+/// CreateDisks返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateDisksResponse {
+    /// 创建的云硬盘ID列表。
+    #[serde(rename = "DiskIdSet")]
+    #[serde(default)]
+    pub disk_id_set: Vec<String>,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DescribeDisks请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeDisksRequest {
+    /// 按照一个或者多个云硬盘ID查询。
+    #[serde(rename = "DiskIds")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_ids: Option<Vec<String>>,
+    /// 过滤条件。参数不支持同时指定`DiskIds`和`Filters`。
+    #[serde(rename = "Filters")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<Filter>>,
+    /// 偏移量，默认为0。
+    #[serde(rename = "Offset")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// 返回数量，默认为20，最大值为100。
+    #[serde(rename = "Limit")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+/// DescribeDisks返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeDisksResponse {
+    /// 符合条件的云硬盘数量。
+    #[serde(rename = "TotalCount")]
+    #[serde(default)]
+    pub total_count: u64,
+    /// 云硬盘的详细信息列表。
+    #[serde(rename = "DiskSet")]
+    #[serde(default)]
+    pub disk_set: Vec<Disk>,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DetachDisks请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetachDisksRequest {
+    /// 将要卸载的云硬盘ID。单次请求最多可卸载10块弹性云盘。
+    #[serde(rename = "DiskIds")]
+    #[serde(default)]
+    pub disk_ids: Vec<String>,
+    /// 对于非共享型云盘，会忽略该参数。
+    #[serde(rename = "InstanceId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+}
+
+/// DetachDisks返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetachDisksResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// 描述了云硬盘的详细信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Disk {
+    /// 云盘是否与挂载的实例一起销毁。
+    #[serde(rename = "DeleteWithInstance")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_with_instance: Option<bool>,
+    /// 自动续费标识。
+    #[serde(rename = "RenewFlag")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renew_flag: Option<String>,
+    /// 硬盘介质类型。
+    #[serde(rename = "DiskType")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_type: Option<String>,
+    /// 云盘状态。
+    #[serde(rename = "DiskState")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_state: Option<String>,
+    /// 云硬盘ID。
+    #[serde(rename = "DiskId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_id: Option<String>,
+    /// 云硬盘名称。
+    #[serde(rename = "DiskName")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_name: Option<String>,
+    /// 云盘是否挂载到云主机上。
+    #[serde(rename = "Attached")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attached: Option<bool>,
+    /// 云硬盘挂载的云主机ID。
+    #[serde(rename = "InstanceId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+    /// 云硬盘大小，单位GB。
+    #[serde(rename = "DiskSize")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_size: Option<u64>,
+    /// 云硬盘所在的位置。
+    #[serde(rename = "Placement")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placement: Option<Placement>,
+    /// 付费模式。
+    #[serde(rename = "DiskChargeType")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_charge_type: Option<String>,
+    /// 云硬盘类型。
+    #[serde(rename = "DiskUsage")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_usage: Option<String>,
+    /// 云硬盘的创建时间。
+    #[serde(rename = "CreateTime")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_time: Option<String>,
+    /// 云硬盘的到期时间。
+    #[serde(rename = "DeadlineTime")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_time: Option<String>,
+    /// 云盘是否为加密盘。
+    #[serde(rename = "Encrypt")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypt: Option<bool>,
+    /// 与云盘绑定的标签，云盘未绑定标签则取值为空。
+    #[serde(rename = "Tags")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+}
+
+/// 描述了实例的计费模式
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskChargePrepaid {
+    /// 购买云盘的时长，默认单位为月，取值范围：1, 2, 3, 4, 5, 6, 7, 8, 9, 12, 24, 36。
+    #[serde(rename = "Period")]
+    #[serde(default)]
+    pub period: u64,
+    /// 自动续费标识。
+    #[serde(rename = "RenewFlag")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renew_flag: Option<String>,
+}
+
+/// 描述键值对过滤器，用于条件过滤查询。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Filter {
+    /// 需要过滤的字段。
+    #[serde(rename = "Name")]
+    #[serde(default)]
+    pub name: String,
+    /// 字段的过滤值。
+    #[serde(rename = "Values")]
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+/// 描述了实例的抽象位置，包括其所在的可用区，所属的项目
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Placement {
+    /// 云硬盘所属的[可用区](/document/product/213/15753#ZoneInfo)。
+    #[serde(rename = "Zone")]
+    #[serde(default)]
+    pub zone: String,
+    /// 实例所属项目ID。不填为默认项目。
+    #[serde(rename = "ProjectId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<u64>,
+    /// 围笼Id。
+    #[serde(rename = "CageId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cage_id: Option<String>,
+}
+
+/// 标签键值对
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Tag {
+    /// 标签键
+    #[serde(rename = "Key")]
+    #[serde(default)]
+    pub key: String,
+    /// 标签值
+    #[serde(rename = "Value")]
+    #[serde(default)]
+    pub value: String,
+}
+
+/// TerminateDisks请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TerminateDisksRequest {
+    /// 需退还的云盘ID列表。
+    #[serde(rename = "DiskIds")]
+    #[serde(default)]
+    pub disk_ids: Vec<String>,
+}
+
+/// TerminateDisks返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TerminateDisksResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl_action!(SERVICE, VERSION, "AttachDisks", AttachDisksRequest => AttachDisksResponse);
+impl_action!(SERVICE, VERSION, "CreateDisks", CreateDisksRequest => CreateDisksResponse);
+impl_action!(SERVICE, VERSION, "DescribeDisks", DescribeDisksRequest => DescribeDisksResponse);
+impl_action!(SERVICE, VERSION, "DetachDisks", DetachDisksRequest => DetachDisksResponse);
+impl_action!(SERVICE, VERSION, "TerminateDisks", TerminateDisksRequest => TerminateDisksResponse);
+
+pub struct CbsV20170312Builder {
+    client: Arc<TencentCloudBaseClient>,
+}
+
+impl CbsV20170312Builder {
+    pub fn new(client: Arc<TencentCloudBaseClient>) -> Self {
+        Self { client }
+    }
+
+    /// 挂载云硬盘
+    pub async fn attach_disks(
+        &self,
+        region: &Region,
+        request: &AttachDisksRequest,
+    ) -> crate::error::Result<AttachDisksResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 创建云硬盘
+    pub async fn create_disks(
+        &self,
+        region: &Region,
+        request: &CreateDisksRequest,
+    ) -> crate::error::Result<CreateDisksResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 查询云硬盘列表
+    pub async fn describe_disks(
+        &self,
+        region: &Region,
+        request: &DescribeDisksRequest,
+    ) -> crate::error::Result<DescribeDisksResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 卸载云硬盘
+    pub async fn detach_disks(
+        &self,
+        region: &Region,
+        request: &DetachDisksRequest,
+    ) -> crate::error::Result<DetachDisksResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 退还云硬盘
+    pub async fn terminate_disks(
+        &self,
+        region: &Region,
+        request: &TerminateDisksRequest,
+    ) -> crate::error::Result<TerminateDisksResponse> {
+        self.client.execute(region, request).await
+    }
+}
@@ -0,0 +1,620 @@
+// @generated by `cargo xtask codegen`, do not edit.
+// source: cvm/v20170312/api.json
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{impl_action, TencentCloudBaseClient},
+    constant::Region,
+};
+
+pub const SERVICE: &str = "cvm";
+pub const VERSION: &str = "2017-03-12";
+
+pub const DESCRIBE_INSTANCES: &str = "DescribeInstances";
+pub const DESCRIBE_INSTANCES_STATUS: &str = "DescribeInstancesStatus";
+pub const DESCRIBE_REGIONS: &str = "DescribeRegions";
+pub const DESCRIBE_ZONES: &str = "DescribeZones";
+pub const REBOOT_INSTANCES: &str = "RebootInstances";
+pub const START_INSTANCES: &str = "StartInstances";
+pub const STOP_INSTANCES: &str = "StopInstances";
+pub const TERMINATE_INSTANCES: &str = "TerminateInstances";
+
+/// 描述了数据盘的信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataDisk {
+    /// 数据盘大小，单位：GB。
+    #[serde(rename = "DiskSize")]
+    #[serde(default)]
+    pub disk_size: i64,
+    /// 数据盘类型。
+    #[serde(rename = "DiskType")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_type: Option<String>,
+    /// 数据盘ID。
+    #[serde(rename = "DiskId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_id: Option<String>,
+    /// 数据盘是否随子机销毁。
+    #[serde(rename = "DeleteWithInstance")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_with_instance: Option<bool>,
+    /// 数据盘是加密。
+    #[serde(rename = "Encrypt")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypt: Option<bool>,
+}
+
+/// DescribeInstances请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeInstancesRequest {
+    /// 按照一个或者多个实例ID查询。每次请求的实例的上限为100。
+    #[serde(rename = "InstanceIds")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_ids: Option<Vec<String>>,
+    /// 过滤条件，每次请求的`Filters`的上限为10，`Filter.Values`的上限为5。
+    #[serde(rename = "Filters")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<Filter>>,
+    /// 偏移量，默认为0。
+    #[serde(rename = "Offset")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    /// 返回数量，默认为20，最大值为100。
+    #[serde(rename = "Limit")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+}
+
+/// DescribeInstances返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeInstancesResponse {
+    /// 符合条件的实例数量。
+    #[serde(rename = "TotalCount")]
+    #[serde(default)]
+    pub total_count: i64,
+    /// 实例详细信息列表。
+    #[serde(rename = "InstanceSet")]
+    #[serde(default)]
+    pub instance_set: Vec<Instance>,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DescribeInstancesStatus请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeInstancesStatusRequest {
+    /// 按照一个或者多个实例ID查询。每次请求的实例的上限为100。
+    #[serde(rename = "InstanceIds")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_ids: Option<Vec<String>>,
+    /// 偏移量，默认为0。
+    #[serde(rename = "Offset")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    /// 返回数量，默认为20，最大值为100。
+    #[serde(rename = "Limit")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+}
+
+/// DescribeInstancesStatus返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeInstancesStatusResponse {
+    /// 符合条件的实例状态数量。
+    #[serde(rename = "TotalCount")]
+    #[serde(default)]
+    pub total_count: i64,
+    /// 实例状态列表。
+    #[serde(rename = "InstanceStatusSet")]
+    #[serde(default)]
+    pub instance_status_set: Vec<InstanceStatus>,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DescribeRegions请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeRegionsRequest {}
+
+/// DescribeRegions返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeRegionsResponse {
+    /// 地域数量
+    #[serde(rename = "TotalCount")]
+    #[serde(default)]
+    pub total_count: u64,
+    /// 地域列表信息
+    #[serde(rename = "RegionSet")]
+    #[serde(default)]
+    pub region_set: Vec<RegionInfo>,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DescribeZones请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeZonesRequest {}
+
+/// DescribeZones返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeZonesResponse {
+    /// 可用区数量。
+    #[serde(rename = "TotalCount")]
+    #[serde(default)]
+    pub total_count: u64,
+    /// 可用区列表信息。
+    #[serde(rename = "ZoneSet")]
+    #[serde(default)]
+    pub zone_set: Vec<ZoneInfo>,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// 描述键值对过滤器，用于条件过滤查询。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Filter {
+    /// 需要过滤的字段。
+    #[serde(rename = "Name")]
+    #[serde(default)]
+    pub name: String,
+    /// 字段的过滤值。
+    #[serde(rename = "Values")]
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+/// 描述实例的信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Instance {
+    /// 实例所在的位置。
+    #[serde(rename = "Placement")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placement: Option<Placement>,
+    /// 实例`ID`。
+    #[serde(rename = "InstanceId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+    /// 实例机型。
+    #[serde(rename = "InstanceType")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_type: Option<String>,
+    /// 实例的CPU核数，单位：核。
+    #[serde(rename = "CPU")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<i64>,
+    /// 实例内存容量，单位：`GB`。
+    #[serde(rename = "Memory")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<i64>,
+    /// 实例业务状态。
+    #[serde(rename = "RestrictState")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restrict_state: Option<String>,
+    /// 实例名称。
+    #[serde(rename = "InstanceName")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_name: Option<String>,
+    /// 实例计费模式。
+    #[serde(rename = "InstanceChargeType")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_charge_type: Option<String>,
+    /// 实例系统盘信息。
+    #[serde(rename = "SystemDisk")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_disk: Option<SystemDisk>,
+    /// 实例数据盘信息。
+    #[serde(rename = "DataDisks")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_disks: Option<Vec<DataDisk>>,
+    /// 实例主网卡的内网`IP`列表。
+    #[serde(rename = "PrivateIpAddresses")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_ip_addresses: Option<Vec<String>>,
+    /// 实例主网卡的公网`IP`列表。
+    #[serde(rename = "PublicIpAddresses")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_ip_addresses: Option<Vec<String>>,
+    /// 实例带宽信息。
+    #[serde(rename = "InternetAccessible")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internet_accessible: Option<InternetAccessible>,
+    /// 实例所属虚拟私有网络信息。
+    #[serde(rename = "VirtualPrivateCloud")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub virtual_private_cloud: Option<VirtualPrivateCloud>,
+    /// 生产实例所使用的镜像`ID`。
+    #[serde(rename = "ImageId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_id: Option<String>,
+    /// 自动续费标识。
+    #[serde(rename = "RenewFlag")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renew_flag: Option<String>,
+    /// 创建时间。按照`ISO8601`标准表示，并且使用`UTC`时间。
+    #[serde(rename = "CreatedTime")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<String>,
+    /// 到期时间。按照`ISO8601`标准表示，并且使用`UTC`时间。
+    #[serde(rename = "ExpiredTime")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expired_time: Option<String>,
+    /// 操作系统名称。
+    #[serde(rename = "OsName")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_name: Option<String>,
+    /// 实例所属安全组。
+    #[serde(rename = "SecurityGroupIds")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_group_ids: Option<Vec<String>>,
+    /// 实例登录设置。
+    #[serde(rename = "LoginSettings")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub login_settings: Option<LoginSettings>,
+    /// 实例状态。
+    #[serde(rename = "InstanceState")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_state: Option<String>,
+    /// 实例关联的标签列表。
+    #[serde(rename = "Tags")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+    /// 实例的最新操作。
+    #[serde(rename = "LatestOperation")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_operation: Option<String>,
+    /// 实例的最新操作状态。
+    #[serde(rename = "LatestOperationState")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_operation_state: Option<String>,
+    /// 实例最新操作的唯一请求 ID。
+    #[serde(rename = "LatestOperationRequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_operation_request_id: Option<String>,
+    /// 实例的全局唯一ID。
+    #[serde(rename = "Uuid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+}
+
+/// 描述实例的状态。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstanceStatus {
+    /// 实例`ID`。
+    #[serde(rename = "InstanceId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+    /// 实例状态。
+    #[serde(rename = "InstanceState")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_state: Option<String>,
+}
+
+/// 描述了实例的公网可访问性，声明了实例的公网使用计费模式，最大带宽等
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InternetAccessible {
+    /// 网络计费类型。
+    #[serde(rename = "InternetChargeType")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internet_charge_type: Option<String>,
+    /// 公网出带宽上限，单位：Mbps。
+    #[serde(rename = "InternetMaxBandwidthOut")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internet_max_bandwidth_out: Option<i64>,
+    /// 是否分配公网IP。
+    #[serde(rename = "PublicIpAssigned")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_ip_assigned: Option<bool>,
+}
+
+/// 描述了实例登录相关配置与信息。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoginSettings {
+    /// 实例登录密码。
+    #[serde(rename = "Password")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// 密钥ID列表。
+    #[serde(rename = "KeyIds")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_ids: Option<Vec<String>>,
+    /// 保持镜像的原始设置。
+    #[serde(rename = "KeepImageLogin")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_image_login: Option<String>,
+}
+
+/// 描述了实例的抽象位置，包括其所在的可用区，所属的项目，宿主机等
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Placement {
+    /// 实例所属的可用区名称。
+    #[serde(rename = "Zone")]
+    #[serde(default)]
+    pub zone: String,
+    /// 实例所属项目ID。
+    #[serde(rename = "ProjectId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<i64>,
+    /// 实例所属的专用宿主机ID列表。
+    #[serde(rename = "HostIds")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_ids: Option<Vec<String>>,
+    /// 实例所属的专用宿主机ID。
+    #[serde(rename = "HostId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_id: Option<String>,
+}
+
+/// RebootInstances请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RebootInstancesRequest {
+    /// 一个或多个待操作的实例ID。每次请求批量实例的上限为100。
+    #[serde(rename = "InstanceIds")]
+    #[serde(default)]
+    pub instance_ids: Vec<String>,
+    /// 关机类型。取值范围：SOFT_FIRST、HARD、SOFT。
+    #[serde(rename = "StopType")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_type: Option<String>,
+}
+
+/// RebootInstances返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RebootInstancesResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// 描述地域信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RegionInfo {
+    /// 地域名称，例如，ap-guangzhou
+    #[serde(rename = "Region")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// 地域描述，例如，华南地区(广州)
+    #[serde(rename = "RegionName")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region_name: Option<String>,
+    /// 地域是否可用状态
+    #[serde(rename = "RegionState")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region_state: Option<String>,
+}
+
+/// StartInstances请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StartInstancesRequest {
+    /// 一个或多个待操作的实例ID。每次请求批量实例的上限为100。
+    #[serde(rename = "InstanceIds")]
+    #[serde(default)]
+    pub instance_ids: Vec<String>,
+}
+
+/// StartInstances返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StartInstancesResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// StopInstances请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StopInstancesRequest {
+    /// 一个或多个待操作的实例ID。每次请求批量实例的上限为100。
+    #[serde(rename = "InstanceIds")]
+    #[serde(default)]
+    pub instance_ids: Vec<String>,
+    /// 实例的关闭模式。取值范围：SOFT_FIRST、HARD、SOFT。
+    #[serde(rename = "StopType")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_type: Option<String>,
+    /// 按量计费实例关机收费模式。
+    #[serde(rename = "StoppedMode")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopped_mode: Option<String>,
+}
+
+/// StopInstances返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StopInstancesResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// 描述了操作系统所在块设备即系统盘的信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemDisk {
+    /// 系统盘类型。
+    #[serde(rename = "DiskType")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_type: Option<String>,
+    /// 系统盘ID。
+    #[serde(rename = "DiskId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_id: Option<String>,
+    /// 系统盘大小，单位：GB。
+    #[serde(rename = "DiskSize")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_size: Option<i64>,
+}
+
+/// 标签键值对
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Tag {
+    /// 标签键
+    #[serde(rename = "Key")]
+    #[serde(default)]
+    pub key: String,
+    /// 标签值
+    #[serde(rename = "Value")]
+    #[serde(default)]
+    pub value: String,
+}
+
+/// TerminateInstances请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TerminateInstancesRequest {
+    /// 一个或多个待操作的实例ID。每次请求批量实例的上限为100。
+    #[serde(rename = "InstanceIds")]
+    #[serde(default)]
+    pub instance_ids: Vec<String>,
+}
+
+/// TerminateInstances返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TerminateInstancesResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// 描述了VPC相关信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VirtualPrivateCloud {
+    /// 私有网络ID
+    #[serde(rename = "VpcId")]
+    #[serde(default)]
+    pub vpc_id: String,
+    /// 私有网络子网ID
+    #[serde(rename = "SubnetId")]
+    #[serde(default)]
+    pub subnet_id: String,
+    /// 是否用作公网网关。
+    #[serde(rename = "AsVpcGateway")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_vpc_gateway: Option<bool>,
+    /// 私有网络子网 IP 数组。
+    #[serde(rename = "PrivateIpAddresses")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_ip_addresses: Option<Vec<String>>,
+}
+
+/// 可用区信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ZoneInfo {
+    /// 可用区名称，例如，ap-guangzhou-3
+    #[serde(rename = "Zone")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>,
+    /// 可用区描述，例如，广州三区
+    #[serde(rename = "ZoneName")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone_name: Option<String>,
+    /// 可用区ID
+    #[serde(rename = "ZoneId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone_id: Option<String>,
+    /// 可用区状态，包含AVAILABLE和UNAVAILABLE。
+    #[serde(rename = "ZoneState")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone_state: Option<String>,
+}
+
+impl_action!(SERVICE, VERSION, "DescribeInstances", DescribeInstancesRequest => DescribeInstancesResponse);
+impl_action!(SERVICE, VERSION, "DescribeInstancesStatus", DescribeInstancesStatusRequest => DescribeInstancesStatusResponse);
+impl_action!(SERVICE, VERSION, "DescribeRegions", DescribeRegionsRequest => DescribeRegionsResponse);
+impl_action!(SERVICE, VERSION, "DescribeZones", DescribeZonesRequest => DescribeZonesResponse);
+impl_action!(SERVICE, VERSION, "RebootInstances", RebootInstancesRequest => RebootInstancesResponse);
+impl_action!(SERVICE, VERSION, "StartInstances", StartInstancesRequest => StartInstancesResponse);
+impl_action!(SERVICE, VERSION, "StopInstances", StopInstancesRequest => StopInstancesResponse);
+impl_action!(SERVICE, VERSION, "TerminateInstances", TerminateInstancesRequest => TerminateInstancesResponse);
+
+pub struct CvmV20170312Builder {
+    client: Arc<TencentCloudBaseClient>,
+}
+
+impl CvmV20170312Builder {
+    pub fn new(client: Arc<TencentCloudBaseClient>) -> Self {
+        Self { client }
+    }
+
+    /// 查看实例列表
+    pub async fn describe_instances(
+        &self,
+        region: &Region,
+        request: &DescribeInstancesRequest,
+    ) -> crate::error::Result<DescribeInstancesResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 查看实例状态列表
+    pub async fn describe_instances_status(
+        &self,
+        region: &Region,
+        request: &DescribeInstancesStatusRequest,
+    ) -> crate::error::Result<DescribeInstancesStatusResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 查询地域列表
+    pub async fn describe_regions(
+        &self,
+        region: &Region,
+        request: &DescribeRegionsRequest,
+    ) -> crate::error::Result<DescribeRegionsResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 查询可用区列表
+    pub async fn describe_zones(
+        &self,
+        region: &Region,
+        request: &DescribeZonesRequest,
+    ) -> crate::error::Result<DescribeZonesResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 重启实例
+    pub async fn reboot_instances(
+        &self,
+        region: &Region,
+        request: &RebootInstancesRequest,
+    ) -> crate::error::Result<RebootInstancesResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 启动实例
+    pub async fn start_instances(
+        &self,
+        region: &Region,
+        request: &StartInstancesRequest,
+    ) -> crate::error::Result<StartInstancesResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 关闭实例
+    pub async fn stop_instances(
+        &self,
+        region: &Region,
+        request: &StopInstancesRequest,
+    ) -> crate::error::Result<StopInstancesResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 退还实例
+    pub async fn terminate_instances(
+        &self,
+        region: &Region,
+        request: &TerminateInstancesRequest,
+    ) -> crate::error::Result<TerminateInstancesResponse> {
+        self.client.execute(region, request).await
+    }
+}
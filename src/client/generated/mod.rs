@@ -0,0 +1,20 @@
+// @generated by `cargo xtask codegen`, do not edit.
+
+pub mod cbs_v20170312;
+pub mod cvm_v20170312;
+pub mod vpc_v20170312;
+
+impl super::TencentCloudClient {
+    /// cbs 2017-03-12
+    pub fn cbs_v20170312(&self) -> cbs_v20170312::CbsV20170312Builder {
+        cbs_v20170312::CbsV20170312Builder::new(self.client.clone())
+    }
+    /// cvm 2017-03-12
+    pub fn cvm_v20170312(&self) -> cvm_v20170312::CvmV20170312Builder {
+        cvm_v20170312::CvmV20170312Builder::new(self.client.clone())
+    }
+    /// vpc 2017-03-12
+    pub fn vpc_v20170312(&self) -> vpc_v20170312::VpcV20170312Builder {
+        vpc_v20170312::VpcV20170312Builder::new(self.client.clone())
+    }
+}
@@ -0,0 +1,522 @@
+// @generated by `cargo xtask codegen`, do not edit.
+// source: vpc/v20170312/api.json
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{impl_action, TencentCloudBaseClient},
+    constant::Region,
+};
+
+pub const SERVICE: &str = "vpc";
+pub const VERSION: &str = "2017-03-12";
+
+pub const CREATE_SECURITY_GROUP: &str = "CreateSecurityGroup";
+pub const CREATE_SECURITY_GROUP_POLICIES: &str = "CreateSecurityGroupPolicies";
+pub const DELETE_SECURITY_GROUP: &str = "DeleteSecurityGroup";
+pub const DELETE_SECURITY_GROUP_POLICIES: &str = "DeleteSecurityGroupPolicies";
+pub const DESCRIBE_SECURITY_GROUP_POLICIES: &str = "DescribeSecurityGroupPolicies";
+pub const DESCRIBE_SECURITY_GROUPS: &str = "DescribeSecurityGroups";
+pub const DESCRIBE_VPCS: &str = "DescribeVpcs";
+pub const REPLACE_SECURITY_GROUP_POLICIES: &str = "ReplaceSecurityGroupPolicies";
+
+/// IP地址模板
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AddressTemplateSpecification {
+    /// IP地址ID，例如：ipm-2uw6ujo6。
+    #[serde(rename = "AddressId")]
+    #[serde(default)]
+    pub address_id: String,
+    /// IP地址组ID，例如：ipmg-2uw6ujo6。
+    #[serde(rename = "AddressGroupId")]
+    #[serde(default)]
+    pub address_group_id: String,
+}
+
+/// CreateSecurityGroupPolicies请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateSecurityGroupPoliciesRequest {
+    /// 安全组实例ID，例如sg-33ocnj9n，可通过DescribeSecurityGroups获取。
+    #[serde(rename = "SecurityGroupId")]
+    #[serde(default)]
+    pub security_group_id: String,
+    /// 安全组规则集合。
+    #[serde(rename = "SecurityGroupPolicySet")]
+    #[serde(default)]
+    pub security_group_policy_set: SecurityGroupPolicySet,
+}
+
+/// CreateSecurityGroupPolicies返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateSecurityGroupPoliciesResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// CreateSecurityGroup请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateSecurityGroupRequest {
+    /// 安全组名称，可任意命名，但不得超过60个字符。
+    #[serde(rename = "GroupName")]
+    #[serde(default)]
+    pub group_name: String,
+    /// 安全组备注，最多100个字符。
+    #[serde(rename = "GroupDescription")]
+    #[serde(default)]
+    pub group_description: String,
+    /// 项目ID，默认0。
+    #[serde(rename = "ProjectId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// 指定绑定的标签列表。
+    #[serde(rename = "Tags")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+}
+
+/// CreateSecurityGroup返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateSecurityGroupResponse {
+    /// 安全组对象。
+    #[serde(rename = "SecurityGroup")]
+    #[serde(default)]
+    pub security_group: SecurityGroup,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DeleteSecurityGroupPolicies请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeleteSecurityGroupPoliciesRequest {
+    /// 安全组实例ID，例如sg-33ocnj9n，可通过DescribeSecurityGroups获取。
+    #[serde(rename = "SecurityGroupId")]
+    #[serde(default)]
+    pub security_group_id: String,
+    /// 安全组规则集合。
+    #[serde(rename = "SecurityGroupPolicySet")]
+    #[serde(default)]
+    pub security_group_policy_set: SecurityGroupPolicySet,
+}
+
+/// DeleteSecurityGroupPolicies返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeleteSecurityGroupPoliciesResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DeleteSecurityGroup请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeleteSecurityGroupRequest {
+    /// 安全组实例ID，例如esg-33ocnj9n，可通过DescribeSecurityGroups获取。
+    #[serde(rename = "SecurityGroupId")]
+    #[serde(default)]
+    pub security_group_id: String,
+}
+
+/// DeleteSecurityGroup返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeleteSecurityGroupResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DescribeSecurityGroupPolicies请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeSecurityGroupPoliciesRequest {
+    /// 安全组实例ID，例如：sg-33ocnj9n，可通过DescribeSecurityGroups获取。
+    #[serde(rename = "SecurityGroupId")]
+    #[serde(default)]
+    pub security_group_id: String,
+    /// 过滤条件。
+    #[serde(rename = "Filters")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<Filter>>,
+}
+
+/// DescribeSecurityGroupPolicies返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeSecurityGroupPoliciesResponse {
+    /// 安全组规则集合。
+    #[serde(rename = "SecurityGroupPolicySet")]
+    #[serde(default)]
+    pub security_group_policy_set: SecurityGroupPolicySet,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DescribeSecurityGroups请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeSecurityGroupsRequest {
+    /// 安全组实例ID，例如：sg-33ocnj9n。每次请求的实例的上限为100。
+    #[serde(rename = "SecurityGroupIds")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_group_ids: Option<Vec<String>>,
+    /// 过滤条件，参数不支持同时指定SecurityGroupIds和Filters。
+    #[serde(rename = "Filters")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<Filter>>,
+    /// 偏移量，默认为0。
+    #[serde(rename = "Offset")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<String>,
+    /// 返回数量，默认为20，最大值为100。
+    #[serde(rename = "Limit")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
+
+/// DescribeSecurityGroups返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeSecurityGroupsResponse {
+    /// 安全组对象。
+    #[serde(rename = "SecurityGroupSet")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_group_set: Option<Vec<SecurityGroup>>,
+    /// 符合条件的实例数量。
+    #[serde(rename = "TotalCount")]
+    #[serde(default)]
+    pub total_count: u64,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// DescribeVpcs请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeVpcsRequest {
+    /// `VPC`实例`ID`。形如：`vpc-f49l6u0z`。每次请求的实例的上限为100。
+    #[serde(rename = "VpcIds")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vpc_ids: Option<Vec<String>>,
+    /// 过滤条件，不支持同时指定`VpcIds`和`Filters`参数。
+    #[serde(rename = "Filters")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<Filter>>,
+    /// 偏移量，默认为0。
+    #[serde(rename = "Offset")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<String>,
+    /// 返回数量，默认为20，最大值为100。
+    #[serde(rename = "Limit")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
+
+/// DescribeVpcs返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DescribeVpcsResponse {
+    /// 符合条件的对象数。
+    #[serde(rename = "TotalCount")]
+    #[serde(default)]
+    pub total_count: u64,
+    /// `VPC`对象。
+    #[serde(rename = "VpcSet")]
+    #[serde(default)]
+    pub vpc_set: Vec<Vpc>,
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// 描述键值对过滤器，用于条件过滤查询。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Filter {
+    /// 需要过滤的字段。
+    #[serde(rename = "Name")]
+    #[serde(default)]
+    pub name: String,
+    /// 字段的过滤值。
+    #[serde(rename = "Values")]
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+/// ReplaceSecurityGroupPolicies请求参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplaceSecurityGroupPoliciesRequest {
+    /// 安全组实例ID，例如sg-33ocnj9n，可通过DescribeSecurityGroups获取。
+    #[serde(rename = "SecurityGroupId")]
+    #[serde(default)]
+    pub security_group_id: String,
+    /// 安全组规则集合对象。
+    #[serde(rename = "SecurityGroupPolicySet")]
+    #[serde(default)]
+    pub security_group_policy_set: SecurityGroupPolicySet,
+    /// 旧的安全组规则集合对象，可选，日志记录用。
+    #[serde(rename = "OriginalSecurityGroupPolicySet")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_security_group_policy_set: Option<SecurityGroupPolicySet>,
+}
+
+/// ReplaceSecurityGroupPolicies返回参数结构体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplaceSecurityGroupPoliciesResponse {
+    /// 唯一请求 ID，每次请求都会返回。定位问题时需要提供该次请求的 RequestId。
+    #[serde(rename = "RequestId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// 安全组对象
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecurityGroup {
+    /// 安全组实例ID，例如：sg-ohuuioma。
+    #[serde(rename = "SecurityGroupId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_group_id: Option<String>,
+    /// 安全组名称，可任意命名，但不得超过60个字符。
+    #[serde(rename = "SecurityGroupName")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_group_name: Option<String>,
+    /// 安全组备注，最多100个字符。
+    #[serde(rename = "SecurityGroupDesc")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_group_desc: Option<String>,
+    /// 项目id，默认0。可在qcloud控制台项目管理页面查询到。
+    #[serde(rename = "ProjectId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// 是否是默认安全组，默认安全组不支持删除。
+    #[serde(rename = "IsDefault")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+    /// 安全组创建时间。
+    #[serde(rename = "CreatedTime")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<String>,
+    /// 标签键值对。
+    #[serde(rename = "TagSet")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_set: Option<Vec<Tag>>,
+    /// 安全组更新时间。
+    #[serde(rename = "UpdateTime")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_time: Option<String>,
+}
+
+/// 安全组规则对象
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecurityGroupPolicy {
+    /// 安全组规则索引号，值会随着安全组规则的变更动态变化。
+    #[serde(rename = "PolicyIndex")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_index: Option<i64>,
+    /// 协议, 取值: TCP,UDP,ICMP,ICMPv6,ALL。
+    #[serde(rename = "Protocol")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    /// 端口(all, 离散port,  range)。
+    #[serde(rename = "Port")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    /// 协议端口ID或者协议端口组ID。ServiceTemplate和Protocol+Port互斥。
+    #[serde(rename = "ServiceTemplate")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_template: Option<ServiceTemplateSpecification>,
+    /// 网段或IP(互斥)。
+    #[serde(rename = "CidrBlock")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cidr_block: Option<String>,
+    /// 网段或IPv6(互斥)。
+    #[serde(rename = "Ipv6CidrBlock")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv6_cidr_block: Option<String>,
+    /// 安全组实例ID，例如：sg-ohuuioma。
+    #[serde(rename = "SecurityGroupId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_group_id: Option<String>,
+    /// IP地址ID或者IP地址组ID。
+    #[serde(rename = "AddressTemplate")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_template: Option<AddressTemplateSpecification>,
+    /// ACCEPT 或 DROP。
+    #[serde(rename = "Action")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    /// 安全组规则描述。
+    #[serde(rename = "PolicyDescription")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_description: Option<String>,
+    /// 安全组最近修改时间。
+    #[serde(rename = "ModifyTime")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modify_time: Option<String>,
+}
+
+/// 安全组规则集合
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecurityGroupPolicySet {
+    /// 安全组规则当前版本。用户每次更新安全规则版本会自动加1，防止更新的路由规则已过期，不填不考虑冲突。
+    #[serde(rename = "Version")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// 出站规则。
+    #[serde(rename = "Egress")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress: Option<Vec<SecurityGroupPolicy>>,
+    /// 入站规则。
+    #[serde(rename = "Ingress")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingress: Option<Vec<SecurityGroupPolicy>>,
+}
+
+/// 协议端口模板
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServiceTemplateSpecification {
+    /// 协议端口ID，例如：ppm-f5n1f8da。
+    #[serde(rename = "ServiceId")]
+    #[serde(default)]
+    pub service_id: String,
+    /// 协议端口组ID，例如：ppmg-f5n1f8da。
+    #[serde(rename = "ServiceGroupId")]
+    #[serde(default)]
+    pub service_group_id: String,
+}
+
+/// 标签键值对
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Tag {
+    /// 标签键
+    #[serde(rename = "Key")]
+    #[serde(default)]
+    pub key: String,
+    /// 标签值
+    #[serde(rename = "Value")]
+    #[serde(default)]
+    pub value: String,
+}
+
+/// 私有网络(VPC)对象。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Vpc {
+    /// `VPC`名称。
+    #[serde(rename = "VpcName")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vpc_name: Option<String>,
+    /// `VPC`实例`ID`，例如：vpc-azd4dt1c。
+    #[serde(rename = "VpcId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vpc_id: Option<String>,
+    /// `VPC`的`IPv4` `CIDR`。
+    #[serde(rename = "CidrBlock")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cidr_block: Option<String>,
+    /// 是否默认`VPC`。
+    #[serde(rename = "IsDefault")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+    /// 创建时间。
+    #[serde(rename = "CreatedTime")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<String>,
+    /// 标签键值对
+    #[serde(rename = "TagSet")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_set: Option<Vec<Tag>>,
+}
+
+impl_action!(SERVICE, VERSION, "CreateSecurityGroup", CreateSecurityGroupRequest => CreateSecurityGroupResponse);
+impl_action!(SERVICE, VERSION, "CreateSecurityGroupPolicies", CreateSecurityGroupPoliciesRequest => CreateSecurityGroupPoliciesResponse);
+impl_action!(SERVICE, VERSION, "DeleteSecurityGroup", DeleteSecurityGroupRequest => DeleteSecurityGroupResponse);
+impl_action!(SERVICE, VERSION, "DeleteSecurityGroupPolicies", DeleteSecurityGroupPoliciesRequest => DeleteSecurityGroupPoliciesResponse);
+impl_action!(SERVICE, VERSION, "DescribeSecurityGroupPolicies", DescribeSecurityGroupPoliciesRequest => DescribeSecurityGroupPoliciesResponse);
+impl_action!(SERVICE, VERSION, "DescribeSecurityGroups", DescribeSecurityGroupsRequest => DescribeSecurityGroupsResponse);
+impl_action!(SERVICE, VERSION, "DescribeVpcs", DescribeVpcsRequest => DescribeVpcsResponse);
+impl_action!(SERVICE, VERSION, "ReplaceSecurityGroupPolicies", ReplaceSecurityGroupPoliciesRequest => ReplaceSecurityGroupPoliciesResponse);
+
+pub struct VpcV20170312Builder {
+    client: Arc<TencentCloudBaseClient>,
+}
+
+impl VpcV20170312Builder {
+    pub fn new(client: Arc<TencentCloudBaseClient>) -> Self {
+        Self { client }
+    }
+
+    /// 创建安全组
+    pub async fn create_security_group(
+        &self,
+        region: &Region,
+        request: &CreateSecurityGroupRequest,
+    ) -> crate::error::Result<CreateSecurityGroupResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 添加安全组规则
+    pub async fn create_security_group_policies(
+        &self,
+        region: &Region,
+        request: &CreateSecurityGroupPoliciesRequest,
+    ) -> crate::error::Result<CreateSecurityGroupPoliciesResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 删除安全组
+    pub async fn delete_security_group(
+        &self,
+        region: &Region,
+        request: &DeleteSecurityGroupRequest,
+    ) -> crate::error::Result<DeleteSecurityGroupResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 删除安全组规则
+    pub async fn delete_security_group_policies(
+        &self,
+        region: &Region,
+        request: &DeleteSecurityGroupPoliciesRequest,
+    ) -> crate::error::Result<DeleteSecurityGroupPoliciesResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 查询安全组规则
+    pub async fn describe_security_group_policies(
+        &self,
+        region: &Region,
+        request: &DescribeSecurityGroupPoliciesRequest,
+    ) -> crate::error::Result<DescribeSecurityGroupPoliciesResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 查看安全组
+    pub async fn describe_security_groups(
+        &self,
+        region: &Region,
+        request: &DescribeSecurityGroupsRequest,
+    ) -> crate::error::Result<DescribeSecurityGroupsResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 查询VPC列表
+    pub async fn describe_vpcs(
+        &self,
+        region: &Region,
+        request: &DescribeVpcsRequest,
+    ) -> crate::error::Result<DescribeVpcsResponse> {
+        self.client.execute(region, request).await
+    }
+
+    /// 批量修改安全组规则
+    pub async fn replace_security_group_policies(
+        &self,
+        region: &Region,
+        request: &ReplaceSecurityGroupPoliciesRequest,
+    ) -> crate::error::Result<ReplaceSecurityGroupPoliciesResponse> {
+        self.client.execute(region, request).await
+    }
+}
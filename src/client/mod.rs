@@ -1,15 +1,40 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use reqwest::header::{self};
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, RequestBuilder};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, Middleware, RequestBuilder};
+use serde::{de::DeserializeOwned, Serialize};
 
+mod action;
+mod credential;
+mod paginate;
+mod rate_limit;
+mod response;
+mod retry;
 mod signature;
+pub(crate) use action::impl_action;
+pub use action::Action;
+pub use credential::{
+    CachedCredentialProvider, Credential, CredentialChain, CredentialProvider,
+    CvmRoleCredentialProvider, EnvCredentialProvider, ProfileCredentialProvider,
+    StaticCredentialProvider, StsAssumeRoleProvider,
+};
+pub(crate) use paginate::impl_paginate;
+pub use paginate::{Paginate, Paginator};
+pub use rate_limit::{Quota, RateLimitConfig, RateLimitMiddleware};
+pub(crate) use response::handle_response;
+pub use response::ApiError;
+pub use retry::{RetryMiddleware, RetryPolicy};
 use signature::{SignatureContext, SignatureMiddleware};
 
-use crate::config::ClientConfig;
+use crate::{
+    config::ClientConfig,
+    constant::{Language, Region},
+    error::Result,
+};
 
 mod constant;
 pub mod cvm;
+pub mod generated;
 pub mod lighthouse;
 
 pub use constant::*;
@@ -21,11 +46,26 @@ pub struct TencentCloudClient {
 
 impl TencentCloudClient {
     pub fn new(config: &ClientConfig) -> Self {
+        Self::builder(config)
+            .build()
+            .expect("failed to build tencent cloud client")
+    }
+    pub fn builder(config: &ClientConfig) -> TencentCloudClientBuilder {
+        TencentCloudClientBuilder::new(config)
+    }
+    /// builder using the default [`CredentialChain`]: env vars, profile file, CVM role.
+    pub fn default_chain_builder() -> TencentCloudClientBuilder {
+        TencentCloudClientBuilder::with_credential_provider(CachedCredentialProvider::new(
+            CredentialChain::default(),
+        ))
+    }
+    /// same client with error messages in `language`, e.g. for a single call:
+    /// `client.with_language(Language::EnUS).cvm()...`
+    pub fn with_language(&self, language: Language) -> Self {
+        let mut client = self.client.as_ref().clone();
+        client.language = Some(language);
         Self {
-            client: Arc::new(TencentCloudBaseClient::new(
-                config.ak.clone(),
-                config.sk.clone(),
-            )),
+            client: Arc::new(client),
         }
     }
     pub fn cvm(&self) -> cvm::CVMBuilder {
@@ -34,47 +74,268 @@ impl TencentCloudClient {
     pub fn lighthouse(&self) -> lighthouse::LighthouseBuilder {
         lighthouse::LighthouseBuilder::new(self.client.clone())
     }
+    /// send any typed [`Action`], see [`TencentCloudBaseClient::execute`].
+    pub async fn execute<A: Action>(&self, region: &Region, request: &A) -> Result<A::Response> {
+        self.client.execute(region, request).await
+    }
+    /// stream every item of a paged `Describe*` action, see [`Paginator`].
+    pub fn paginate<A: Paginate>(&self, region: &Region, request: A) -> Paginator<A> {
+        Paginator::new(self.client.clone(), region.clone(), request)
+    }
+    /// call any action with a json body like `tccli` does, returns the content of `Response`.
+    ///
+    /// `region` is `None` for global services such as `cam`, which do not take `X-TC-Region`.
+    pub async fn call(
+        &self,
+        service: &str,
+        version: &str,
+        action: &str,
+        region: Option<Region>,
+        body: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        self.client
+            .call(service, version, action, region.as_ref(), &body)
+            .await
+    }
+}
+
+const DEFAULT_ENDPOINT: &str = "https://tencentcloudapi.com";
+
+pub struct TencentCloudClientBuilder {
+    credential: Arc<dyn CredentialProvider>,
+    endpoints: EndpointResolver,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<reqwest::Proxy>,
+    user_agent: Option<String>,
+    language: Option<Language>,
+    retry: Option<RetryPolicy>,
+    rate_limit: Option<RateLimitConfig>,
+    middlewares: Vec<Arc<dyn Middleware>>,
 }
 
-#[derive(Debug)]
+impl TencentCloudClientBuilder {
+    pub fn new(config: &ClientConfig) -> Self {
+        Self::with_credential_provider(StaticCredentialProvider::from(config))
+    }
+
+    /// builder signing with credentials from `provider` instead of a fixed ak/sk.
+    pub fn with_credential_provider(provider: impl CredentialProvider + 'static) -> Self {
+        Self {
+            credential: Arc::new(provider),
+            endpoints: EndpointResolver::default(),
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            user_agent: None,
+            language: None,
+            retry: None,
+            rate_limit: None,
+            middlewares: Vec::new(),
+        }
+    }
+
+    /// root endpoint that the service name is prefixed to, default `https://tencentcloudapi.com`.
+    ///
+    /// the scheme is kept, and ip or `localhost` roots such as `http://127.0.0.1:8080` are used
+    /// verbatim without service prefix, so every service talks to a local mock or proxy.
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoints.root = endpoint.into();
+        self
+    }
+
+    /// endpoint kind used for every service without its own override, default [`Endpoint::Global`].
+    pub fn default_endpoint(mut self, endpoint: Endpoint) -> Self {
+        self.endpoints.default = endpoint;
+        self
+    }
+
+    /// endpoint kind of one service, e.g. `Endpoint::Regional` for `cvm`.
+    pub fn service_endpoint(mut self, service: &str, endpoint: Endpoint) -> Self {
+        self.endpoints.services.insert(service.to_owned(), endpoint);
+        self
+    }
+
+    /// total timeout of each request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// language of error messages, server default is `zh-CN`.
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// retry network errors, 5xx, `RequestLimitExceeded` and `InternalError`, disabled by default.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// queue requests locally per (service, action) instead of hitting server side throttling.
+    pub fn rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limit = Some(config);
+        self
+    }
+
+    /// extra middleware layer, runs after signing in the order they are added.
+    pub fn with<M: Middleware>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    pub fn build(self) -> Result<TencentCloudClient> {
+        let mut reqwest_builder = reqwest::Client::builder();
+        if let Some(timeout) = self.timeout {
+            reqwest_builder = reqwest_builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            reqwest_builder = reqwest_builder.connect_timeout(timeout);
+        }
+        if let Some(proxy) = self.proxy {
+            reqwest_builder = reqwest_builder.proxy(proxy);
+        }
+        if let Some(user_agent) = self.user_agent {
+            reqwest_builder = reqwest_builder.user_agent(user_agent);
+        }
+
+        let mut builder = ClientBuilder::new(reqwest_builder.build()?);
+        if let Some(policy) = self.retry {
+            builder = builder.with(RetryMiddleware::new(policy));
+        }
+        if let Some(config) = self.rate_limit {
+            builder = builder.with(RateLimitMiddleware::new(config));
+        }
+        builder = builder.with(SignatureMiddleware);
+        for middleware in self.middlewares {
+            builder = builder.with_arc(middleware);
+        }
+        Ok(TencentCloudClient {
+            client: Arc::new(TencentCloudBaseClient {
+                client: builder.build(),
+                credential: self.credential,
+                endpoints: self.endpoints,
+                language: self.language,
+            }),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct TencentCloudBaseClient {
     client: ClientWithMiddleware,
-    ak: String,
-    sk: String,
-    base_url: String,
+    credential: Arc<dyn CredentialProvider>,
+    endpoints: EndpointResolver,
+    language: Option<Language>,
 }
 
 impl TencentCloudBaseClient {
     pub fn new(ak: String, sk: String) -> Self {
-        let reqwest_client = reqwest::Client::new();
-        let client = ClientBuilder::new(reqwest_client)
+        let client = ClientBuilder::new(reqwest::Client::new())
             .with(SignatureMiddleware)
             .build();
         Self {
             client,
-            ak,
-            sk,
-            base_url: "https://tencentcloudapi.com".to_owned(),
+            credential: Arc::new(StaticCredentialProvider::new(Credential::new(ak, sk))),
+            endpoints: EndpointResolver::default(),
+            language: None,
         }
-        // base_url: "https://cvm.tencentcloudapi.com".to_owned(),
     }
 
-    pub fn get(&self, service: &str, version: &str) -> RequestBuilder {
-        self.client
-            .get(build_service_api_url(&self.base_url, service))
-            .with_extension(self.signature_context(service, version))
+    /// `region` picks the regional endpoint and sets `X-TC-Region`, global services pass `None`.
+    pub fn get(&self, service: &str, version: &str, region: Option<&Region>) -> RequestBuilder {
+        let builder = self
+            .client
+            .get(self.endpoints.resolve(service, region))
+            .with_extension(self.signature_context(service, version));
+        self.with_common_headers(builder, region)
     }
 
-    pub fn post(&self, service: &str, version: &str) -> RequestBuilder {
-        self.client
-            .post(build_service_api_url(&self.base_url, service))
-            .with_extension(self.signature_context(service, version))
+    /// `region` picks the regional endpoint and sets `X-TC-Region`, global services pass `None`.
+    pub fn post(&self, service: &str, version: &str, region: Option<&Region>) -> RequestBuilder {
+        let builder = self
+            .client
+            .post(self.endpoints.resolve(service, region))
+            .with_extension(self.signature_context(service, version));
+        self.with_common_headers(builder, region)
+    }
+
+    /// sign and send `request`, then decode `Response` or map `Response.Error`.
+    pub async fn execute<A: Action>(&self, region: &Region, request: &A) -> Result<A::Response> {
+        self.send(A::SERVICE, A::VERSION, A::ACTION, Some(region), request)
+            .await
+    }
+
+    /// [`Self::execute`] without `X-TC-Region`, for actions that are not regional such as
+    /// DescribeRegions.
+    pub async fn execute_global<A: Action>(&self, request: &A) -> Result<A::Response> {
+        self.send(A::SERVICE, A::VERSION, A::ACTION, None, request)
+            .await
+    }
+
+    /// untyped [`Self::execute`] for actions without a model, returns the content of `Response`.
+    pub async fn call(
+        &self,
+        service: &str,
+        version: &str,
+        action: &str,
+        region: Option<&Region>,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        self.send(service, version, action, region, body).await
+    }
+
+    async fn send<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        service: &str,
+        version: &str,
+        action: &str,
+        region: Option<&Region>,
+        body: &B,
+    ) -> Result<T> {
+        let resp = self
+            .post(service, version, region)
+            .header(ACTION_HEADER, action)
+            .json(body)
+            .send()
+            .await?;
+        handle_response(resp).await
+    }
+
+    fn with_common_headers(
+        &self,
+        mut builder: RequestBuilder,
+        region: Option<&Region>,
+    ) -> RequestBuilder {
+        if let Some(region) = region {
+            builder = builder.header(REGION_HEADER, region.to_string());
+        }
+        if let Some(language) = self.language {
+            builder = builder.header(LANGUAGE_HEADER, language.to_string());
+        }
+        builder
     }
 
     pub fn signature_context(&self, service: &str, version: &str) -> SignatureContext {
         SignatureContext {
-            ak: self.ak.clone(),
-            sk: self.sk.clone(),
+            credential: self.credential.clone(),
             signed_headers: Some(vec![header::CONTENT_TYPE, header::HOST]),
             service_name: service.to_owned(),
             version: version.to_owned(),
@@ -82,10 +343,142 @@ impl TencentCloudBaseClient {
     }
 }
 
+/// Which host a service is called on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    /// `{service}.tencentcloudapi.com`, routed to the nearest region.
+    Global,
+    /// `{service}.{region}.tencentcloudapi.com`, falls back to global for calls without region.
+    Regional,
+    /// `{service}.intl.tencentcloudapi.com`, for international accounts.
+    Intl,
+    /// `{service}.internal.tencentcloudapi.com`, only reachable from inside tencent cloud.
+    Internal,
+    /// full url used as is, e.g. a private gateway or `http://127.0.0.1:8080` in tests.
+    Custom(String),
+}
+
+/// Resolve the api url of a service from the root endpoint and the configured [`Endpoint`].
+#[derive(Debug, Clone)]
+pub struct EndpointResolver {
+    root: String,
+    default: Endpoint,
+    services: HashMap<String, Endpoint>,
+}
+
+impl Default for EndpointResolver {
+    fn default() -> Self {
+        Self {
+            root: DEFAULT_ENDPOINT.to_owned(),
+            default: Endpoint::Global,
+            services: HashMap::new(),
+        }
+    }
+}
+
+impl EndpointResolver {
+    pub fn resolve(&self, service: &str, region: Option<&Region>) -> String {
+        let host = match (self.services.get(service).unwrap_or(&self.default), region) {
+            (Endpoint::Custom(url), _) => return url.clone(),
+            (Endpoint::Regional, Some(region)) => format!("{service}.{region}"),
+            (Endpoint::Intl, _) => format!("{service}.intl"),
+            (Endpoint::Internal, _) => format!("{service}.internal"),
+            (Endpoint::Global | Endpoint::Regional, _) => service.to_owned(),
+        };
+        build_service_api_url(&self.root, &host)
+    }
+}
+
 fn build_service_api_url(url: &str, service_name: &str) -> String {
-    format!(
-        "https://{}.{}",
-        service_name,
-        &url[url.find("://").map_or(0, |x| x + 3)..]
-    )
+    let (scheme, host) = url.split_once("://").unwrap_or(("https", url));
+    if is_ip_or_localhost(host) {
+        return format!("{scheme}://{host}");
+    }
+    format!("{scheme}://{service_name}.{host}")
+}
+
+/// `127.0.0.1:8080`, `[::1]:8080` or `localhost`, which cannot take a service subdomain.
+fn is_ip_or_localhost(authority: &str) -> bool {
+    let authority = authority.split('/').next().unwrap_or_default();
+    if let Some(ipv6) = authority.strip_prefix('[') {
+        return ipv6
+            .split(']')
+            .next()
+            .is_some_and(|ip| ip.parse::<std::net::Ipv6Addr>().is_ok());
+    }
+    let host = authority
+        .rsplit_once(':')
+        .map_or(authority, |(host, _)| host);
+    host.eq_ignore_ascii_case("localhost") || host.parse::<std::net::Ipv4Addr>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_service_api_url() {
+        assert_eq!(
+            build_service_api_url("https://tencentcloudapi.com", "cvm"),
+            "https://cvm.tencentcloudapi.com"
+        );
+        assert_eq!(
+            build_service_api_url("http://localhost:8080", "cvm"),
+            "http://localhost:8080"
+        );
+        assert_eq!(
+            build_service_api_url("http://127.0.0.1:8080", "cvm"),
+            "http://127.0.0.1:8080"
+        );
+        assert_eq!(
+            build_service_api_url("http://[::1]:8080/", "cvm"),
+            "http://[::1]:8080/"
+        );
+        assert_eq!(
+            build_service_api_url("http://gateway.internal:8080", "cvm"),
+            "http://cvm.gateway.internal:8080"
+        );
+        assert_eq!(
+            build_service_api_url("tencentcloudapi.com", "vpc"),
+            "https://vpc.tencentcloudapi.com"
+        );
+    }
+
+    #[test]
+    fn test_endpoint_resolver() {
+        let mut resolver = EndpointResolver::default();
+        resolver.services.insert("cvm".into(), Endpoint::Regional);
+        resolver.services.insert(
+            "lighthouse".into(),
+            Endpoint::Custom("http://127.0.0.1:8080".into()),
+        );
+        let region = Region::Guangzhou;
+        assert_eq!(
+            resolver.resolve("cvm", Some(&region)),
+            "https://cvm.ap-guangzhou.tencentcloudapi.com"
+        );
+        assert_eq!(
+            resolver.resolve("cvm", None),
+            "https://cvm.tencentcloudapi.com"
+        );
+        assert_eq!(
+            resolver.resolve("vpc", Some(&region)),
+            "https://vpc.tencentcloudapi.com"
+        );
+        assert_eq!(
+            resolver.resolve("lighthouse", Some(&region)),
+            "http://127.0.0.1:8080"
+        );
+
+        resolver.default = Endpoint::Intl;
+        assert_eq!(
+            resolver.resolve("vpc", Some(&region)),
+            "https://vpc.intl.tencentcloudapi.com"
+        );
+        resolver.default = Endpoint::Internal;
+        assert_eq!(
+            resolver.resolve("cam", None),
+            "https://cam.internal.tencentcloudapi.com"
+        );
+    }
 }
@@ -0,0 +1,157 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::{
+    stream::{self, BoxStream},
+    Stream, StreamExt, TryStreamExt,
+};
+
+use crate::{client::Action, constant::Region, error::Result};
+
+use super::TencentCloudBaseClient;
+
+/// A `Describe*` action paged with `Offset`/`Limit`, whose response carries `TotalCount`.
+pub trait Paginate: Action + Clone + Send + Sync + 'static {
+    type Item: Send + 'static;
+
+    /// largest `Limit` the action accepts, used as page size unless the request sets one.
+    const MAX_LIMIT: u64 = 100;
+
+    fn offset(&self) -> Option<u64>;
+    fn limit(&self) -> Option<u64>;
+    fn set_page(&mut self, offset: u64, limit: u64);
+    fn total_count(response: &Self::Response) -> u64;
+    fn into_items(response: Self::Response) -> Vec<Self::Item>;
+}
+
+/// `impl_paginate!(DescribeInstancesRequest, instance_set: Instance);`
+///
+/// the request needs `offset`/`limit: Option<u64>` and the response `total_count`.
+macro_rules! impl_paginate {
+    ($request:ty, $items:ident: $item:ty) => {
+        impl $crate::client::Paginate for $request {
+            type Item = $item;
+
+            fn offset(&self) -> Option<u64> {
+                self.offset
+            }
+            fn limit(&self) -> Option<u64> {
+                self.limit
+            }
+            fn set_page(&mut self, offset: u64, limit: u64) {
+                self.offset = Some(offset);
+                self.limit = Some(limit);
+            }
+            fn total_count(response: &Self::Response) -> u64 {
+                response.total_count as u64
+            }
+            fn into_items(response: Self::Response) -> Vec<$item> {
+                response.$items
+            }
+        }
+    };
+}
+
+pub(crate) use impl_paginate;
+
+/// Stream of every item of a [`Paginate`] action, fetching the next page on demand.
+///
+/// Starts at the `Offset` of the request and stops once `TotalCount` items were seen or a page
+/// comes back empty.
+pub struct Paginator<A: Paginate> {
+    stream: BoxStream<'static, Result<A::Item>>,
+}
+
+impl<A: Paginate> Paginator<A> {
+    pub fn new(client: Arc<TencentCloudBaseClient>, region: Region, mut request: A) -> Self {
+        let offset = request.offset().unwrap_or_default();
+        let limit = request.limit().unwrap_or(A::MAX_LIMIT);
+        let stream = pages(offset, limit, move |offset, limit| {
+            request.set_page(offset, limit);
+            let request = request.clone();
+            let client = client.clone();
+            let region = region.clone();
+            async move {
+                let response = client.execute(&region, &request).await?;
+                Ok((A::total_count(&response), A::into_items(response)))
+            }
+        });
+        Self {
+            stream: stream.boxed(),
+        }
+    }
+
+    /// walk all pages and collect the items.
+    pub async fn collect_all(self) -> Result<Vec<A::Item>> {
+        self.try_collect().await
+    }
+}
+
+impl<A: Paginate> Stream for Paginator<A> {
+    type Item = Result<A::Item>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+/// items of the pages returned by `fetch(offset, limit) -> (total_count, items)`.
+fn pages<T, F, Fut>(offset: u64, limit: u64, fetch: F) -> impl Stream<Item = Result<T>>
+where
+    F: FnMut(u64, u64) -> Fut,
+    Fut: Future<Output = Result<(u64, Vec<T>)>>,
+{
+    stream::try_unfold(
+        (fetch, Some(offset)),
+        move |(mut fetch, offset)| async move {
+            let Some(offset) = offset else {
+                return Result::<Option<_>>::Ok(None);
+            };
+            let (total_count, items) = fetch(offset, limit).await?;
+            let next = offset + items.len() as u64;
+            let next = (!items.is_empty() && next < total_count).then_some(next);
+            Ok(Some((items, (fetch, next))))
+        },
+    )
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pages() {
+        let calls = Mutex::new(vec![]);
+        let items: Vec<u64> = (0..45).collect();
+        let all: Vec<u64> = pages(0, 20, |offset, limit| {
+            calls.lock().unwrap().push((offset, limit));
+            let page = items
+                .iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .copied()
+                .collect();
+            async move { Result::Ok((45, page)) }
+        })
+        .try_collect()
+        .await
+        .unwrap();
+        assert_eq!(all, items);
+        assert_eq!(*calls.lock().unwrap(), vec![(0, 20), (20, 20), (40, 20)]);
+
+        // a short total count must not loop forever
+        let all: Vec<u64> = pages(0, 20, |_, _| async { Result::Ok((100, Vec::<u64>::new())) })
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(all.is_empty());
+    }
+}
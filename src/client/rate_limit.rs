@@ -0,0 +1,175 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next, Result};
+use task_local_extensions::Extensions;
+use tracing::debug;
+
+use super::{signature::SignatureContext, ACTION_HEADER};
+
+/// Allowed request rate of one (service, action) pair.
+#[derive(Debug, Clone, Copy)]
+pub struct Quota {
+    pub per_second: u32,
+    pub burst: u32,
+}
+
+impl Quota {
+    pub fn per_second(per_second: u32) -> Self {
+        Self {
+            per_second,
+            burst: per_second,
+        }
+    }
+}
+
+/// Quotas for [`RateLimitMiddleware`], actions without a quota are not limited unless a default
+/// quota is set.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitConfig {
+    default: Option<Quota>,
+    actions: HashMap<(String, String), Quota>,
+}
+
+impl RateLimitConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// quota for every action that has no explicit quota.
+    pub fn default_quota(mut self, quota: Quota) -> Self {
+        self.default = Some(quota);
+        self
+    }
+
+    pub fn action(mut self, service: &str, action: &str, quota: Quota) -> Self {
+        self.actions
+            .insert((service.to_owned(), action.to_owned()), quota);
+        self
+    }
+
+    fn quota(&self, key: &(String, String)) -> Option<Quota> {
+        self.actions.get(key).copied().or(self.default)
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    quota: Quota,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(quota: Quota, now: Instant) -> Self {
+        Self {
+            quota,
+            tokens: quota.burst as f64,
+            last: now,
+        }
+    }
+
+    /// take one token and return how long the caller has to wait for it.
+    ///
+    /// tokens may go negative, which queues later callers behind earlier ones.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let rate = self.quota.per_second.max(1) as f64;
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(self.quota.burst.max(1) as f64);
+        self.last = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / rate)
+        }
+    }
+}
+
+/// Client side token bucket per (service, action), so tasks sharing one client queue up locally
+/// instead of being throttled with `RequestLimitExceeded`.
+///
+/// Must be installed before `SignatureMiddleware` so the timestamp is taken after waiting.
+pub struct RateLimitMiddleware {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<(String, String), TokenBucket>>,
+}
+
+impl RateLimitMiddleware {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn reserve(&self, key: (String, String)) -> Duration {
+        let Some(quota) = self.config.quota(&key) else {
+            return Duration::ZERO;
+        };
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets
+            .entry(key)
+            .or_insert_with(|| TokenBucket::new(quota, now))
+            .reserve(now)
+    }
+}
+
+#[async_trait]
+impl Middleware for RateLimitMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let service = extensions
+            .get::<SignatureContext>()
+            .map(|c| c.service_name.clone());
+        let action = req
+            .headers()
+            .get(ACTION_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned);
+        if let (Some(service), Some(action)) = (service, action) {
+            let wait = self.reserve((service, action));
+            if !wait.is_zero() {
+                debug!("rate limited, wait {wait:?}");
+                tokio::time::sleep(wait).await;
+            }
+        }
+        next.run(req, extensions).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(
+            Quota {
+                per_second: 10,
+                burst: 2,
+            },
+            now,
+        );
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::from_millis(100));
+        assert_eq!(bucket.reserve(now), Duration::from_millis(200));
+        // refilled after waiting, but never above burst
+        let later = now + Duration::from_secs(10);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+        assert_eq!(bucket.reserve(later), Duration::from_millis(100));
+    }
+}
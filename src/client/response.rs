@@ -0,0 +1,111 @@
+use reqwest::StatusCode;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use tracing::debug;
+
+use crate::{
+    error::{Result, TencentCloudError},
+    error_code::ErrorCode,
+};
+
+/// Error object returned inside `Response.Error` by Tencent Cloud API 3.0.
+///
+/// Failed calls still answer with HTTP 200, so this has to be checked on every response.
+#[derive(Debug, Clone, Deserialize, thiserror::Error)]
+#[serde(rename_all = "PascalCase")]
+#[error("tencent cloud api error {code}: {message} (request id: {request_id})")]
+pub struct ApiError {
+    pub code: String,
+    pub message: String,
+    #[serde(skip)]
+    pub request_id: String,
+}
+
+impl ApiError {
+    pub fn error_code(&self) -> ErrorCode {
+        ErrorCode::from(self.code.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ResponseEnvelope {
+    response: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ResponseError {
+    error: ApiError,
+    #[serde(default)]
+    request_id: String,
+}
+
+/// Check the status, unwrap `{"Response": ...}` and turn `Response.Error` into an [`ApiError`].
+pub(crate) async fn handle_response<T: DeserializeOwned>(resp: reqwest::Response) -> Result<T> {
+    let status = resp.status();
+    let body = resp.text().await?;
+    debug!("body: {body:?}");
+    match status {
+        StatusCode::OK => parse_response(&body),
+        status => Err(TencentCloudError::Status { status, body }),
+    }
+}
+
+pub(crate) fn parse_response<T: DeserializeOwned>(body: &str) -> Result<T> {
+    let decode_err = |source| TencentCloudError::Decode {
+        source,
+        body: body.to_owned(),
+    };
+    let envelope: ResponseEnvelope = serde_json::from_str(body).map_err(decode_err)?;
+    if envelope.response.get("Error").is_some() {
+        let ResponseError {
+            mut error,
+            request_id,
+        } = serde_json::from_value(envelope.response).map_err(decode_err)?;
+        error.request_id = request_id;
+        return Err(error.into());
+    }
+    serde_json::from_value(envelope.response).map_err(decode_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Dummy {
+        total_count: usize,
+        request_id: String,
+    }
+
+    #[test]
+    fn test_parse_ok() {
+        let body = r#"{"Response":{"TotalCount":3,"RequestId":"req-1"}}"#;
+        let r: Dummy = parse_response(body).unwrap();
+        assert_eq!(r.total_count, 3);
+        assert_eq!(r.request_id, "req-1");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let body = r#"{"Response":{"Error":{"Code":"AuthFailure.SignatureExpire","Message":"expired"},"RequestId":"req-2"}}"#;
+        let err = parse_response::<Dummy>(body).unwrap_err();
+        let err = err.api_error().unwrap();
+        assert_eq!(err.code, "AuthFailure.SignatureExpire");
+        assert_eq!(
+            err.error_code(),
+            ErrorCode::AuthFailure(Some(crate::error_code::AuthFailure::SignatureExpire))
+        );
+        assert_eq!(err.message, "expired");
+        assert_eq!(err.request_id, "req-2");
+    }
+
+    #[test]
+    fn test_parse_decode_error() {
+        let body = r#"{"Response":{"RequestId":"req-3"}}"#;
+        let err = parse_response::<Dummy>(body).unwrap_err();
+        assert!(matches!(err, TencentCloudError::Decode { body: b, .. } if b == body));
+    }
+}
@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::{Request, Response, StatusCode};
+use reqwest_middleware::{Middleware, Next, Result};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Deserialize;
+use task_local_extensions::Extensions;
+use tracing::debug;
+
+use crate::error_code::ErrorCode;
+
+/// Retry settings for [`RetryMiddleware`].
+///
+/// The delay before retry `n` (starting at 0) is drawn from
+/// `[backoff / 2, backoff]` where `backoff = min(initial_backoff * 2^n, max_backoff)`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, attempt: u32, rng: &SystemRandom) -> Duration {
+        let exp = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff);
+        let mut buf = [0u8; 4];
+        let jitter = match rng.fill(&mut buf) {
+            Ok(()) => u32::from_le_bytes(buf) as f64 / u32::MAX as f64,
+            Err(_) => 1.0,
+        };
+        exp / 2 + (exp / 2).mul_f64(jitter)
+    }
+}
+
+/// Retries network errors, 5xx and retryable api error codes.
+///
+/// Must be installed before `SignatureMiddleware` so every attempt is signed again with a fresh
+/// `X-TC-Timestamp`.
+pub struct RetryMiddleware {
+    policy: RetryPolicy,
+    rng: SystemRandom,
+}
+
+impl RetryMiddleware {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            rng: SystemRandom::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CodeEnvelope {
+    response: CodeResponse,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CodeResponse {
+    error: Option<CodeError>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CodeError {
+    code: String,
+}
+
+/// Buffer the body to look at `Response.Error.Code`, then hand back an equivalent response.
+async fn peek_error_code(resp: Response) -> Result<(Response, Option<ErrorCode>)> {
+    let status = resp.status();
+    let version = resp.version();
+    let headers = resp.headers().clone();
+    let body = resp.bytes().await?;
+
+    let code = serde_json::from_slice::<CodeEnvelope>(&body)
+        .ok()
+        .and_then(|envelope| envelope.response.error)
+        .map(|error| ErrorCode::from(error.code.as_str()));
+
+    let mut builder = http::Response::builder().status(status).version(version);
+    if let Some(h) = builder.headers_mut() {
+        *h = headers;
+    }
+    let resp = builder
+        .body(body)
+        .map_err(|e| reqwest_middleware::Error::Middleware(e.into()))?;
+    Ok((Response::from(resp), code))
+}
+
+#[async_trait]
+impl Middleware for RetryMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            // streaming bodies can not be replayed
+            let Some(cloned) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+            let (result, retry) = match next.clone().run(cloned, extensions).await {
+                Err(reqwest_middleware::Error::Reqwest(e))
+                    if e.is_connect() || e.is_timeout() || e.is_request() =>
+                {
+                    (Err(reqwest_middleware::Error::Reqwest(e)), true)
+                }
+                Ok(resp) if resp.status().is_server_error() => (Ok(resp), true),
+                Ok(resp) if resp.status() == StatusCode::OK => {
+                    let (resp, code) = peek_error_code(resp).await?;
+                    let retry = code.as_ref().is_some_and(ErrorCode::is_retryable);
+                    (Ok(resp), retry)
+                }
+                rest => (rest, false),
+            };
+            if !retry || attempt >= self.policy.max_retries {
+                return result;
+            }
+            let backoff = self.policy.backoff(attempt, &self.rng);
+            debug!("retry attempt {} after {backoff:?}", attempt + 1);
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+        let rng = SystemRandom::new();
+        for (attempt, upper) in [(0, 100), (1, 200), (2, 400), (3, 500), (10, 500)] {
+            let backoff = policy.backoff(attempt, &rng);
+            assert!(backoff >= Duration::from_millis(upper / 2));
+            assert!(backoff <= Duration::from_millis(upper));
+        }
+    }
+}
@@ -12,11 +12,18 @@ use time::OffsetDateTime;
 
 use sha2::{Digest, Sha256};
 
-use crate::client::constant::ACTION_HEADER;
+use std::sync::Arc;
+
+use crate::{
+    client::{
+        constant::ACTION_HEADER,
+        credential::{Credential, CredentialProvider},
+    },
+    error::TencentCloudError,
+};
 
 pub struct SignatureContext {
-    pub ak: String,
-    pub sk: String,
+    pub credential: Arc<dyn CredentialProvider>,
     pub signed_headers: Option<Vec<HeaderName>>,
     pub service_name: String,
     pub version: String,
@@ -33,7 +40,13 @@ impl Middleware for SignatureMiddleware {
         next: Next<'_>,
     ) -> Result<Response> {
         if let Some(context) = extensions.get::<SignatureContext>() {
-            add_authorization(&mut req, context)?;
+            let credential = context
+                .credential
+                .credential()
+                .await
+                .map_err(|e| reqwest_middleware::Error::Middleware(e.into()))?;
+            add_authorization(&mut req, &credential, context)
+                .map_err(|e| reqwest_middleware::Error::Middleware(e.into()))?;
         }
         next.run(req, extensions).await
     }
@@ -42,24 +55,41 @@ impl Middleware for SignatureMiddleware {
 const ALGORITHM: &str = "TC3-HMAC-SHA256";
 const TIME_HEADER: &str = "X-TC-Timestamp";
 const TC_VERSION: &str = "X-TC-Version";
+const TOKEN_HEADER: &str = "X-TC-Token";
 
 fn hmac_sha256(key: &[u8], data: &[u8]) -> hmac::Tag {
     let key = hmac::Key::new(hmac::HMAC_SHA256, key);
     hmac::sign(&key, data)
 }
 
-pub fn add_authorization(req: &mut Request, context: &SignatureContext) -> anyhow::Result<String> {
+pub fn add_authorization(
+    req: &mut Request,
+    credential: &Credential,
+    context: &SignatureContext,
+) -> std::result::Result<String, TencentCloudError> {
     let date = OffsetDateTime::now_utc().date();
     let ts = OffsetDateTime::now_utc().unix_timestamp();
     req.headers_mut().insert(TIME_HEADER, ts.into());
     req.headers_mut()
         .insert(TC_VERSION, context.version.parse().unwrap());
 
-    let host = req.url().host_str().map(|s| s.to_owned());
+    // keep the port of custom endpoints so the signed host matches what is sent
+    let host = req.url().host_str().map(|host| match req.url().port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_owned(),
+    });
     if let Some(host) = host {
         req.headers_mut()
             .insert(header::HOST, host.parse().unwrap());
     }
+    if let Some(token) = &credential.token {
+        req.headers_mut().insert(
+            TOKEN_HEADER,
+            token
+                .parse()
+                .map_err(|e| TencentCloudError::Signature(format!("invalid token: {e}")))?,
+        );
+    }
     if req.headers().get(header::CONTENT_TYPE).is_none() {
         req.headers_mut()
             .insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
@@ -83,7 +113,7 @@ pub fn add_authorization(req: &mut Request, context: &SignatureContext) -> anyho
     let derived_sk = hmac_sha256(
         hmac_sha256(
             hmac_sha256(
-                format!("TC3{}", context.sk).as_bytes(),
+                format!("TC3{}", credential.secret_key).as_bytes(),
                 format!("{date}").as_bytes(),
             )
             .as_ref(),
@@ -98,11 +128,15 @@ pub fn add_authorization(req: &mut Request, context: &SignatureContext) -> anyho
 
     let authorization = format!(
         "{ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
-        context.ak
+        credential.secret_id
     );
     // println!("authorization:\n{authorization:?}\n");
-    req.headers_mut()
-        .insert(AUTHORIZATION, authorization.parse()?);
+    req.headers_mut().insert(
+        AUTHORIZATION,
+        authorization
+            .parse()
+            .map_err(|e| TencentCloudError::Signature(format!("invalid authorization: {e}")))?,
+    );
 
     Ok(authorization)
 }
@@ -114,7 +148,7 @@ const RFC3986_RESERVED_CHARACTERS: &percent_encoding::AsciiSet =
         .remove(b'~')
         .remove(b'.');
 
-fn precent_encode_rfc3986(data: &[u8]) -> percent_encoding::PercentEncode {
+fn precent_encode_rfc3986(data: &[u8]) -> percent_encoding::PercentEncode<'_> {
     percent_encoding::percent_encode(data, RFC3986_RESERVED_CHARACTERS)
 }
 
@@ -151,7 +185,7 @@ fn get_hashed_request(req: &Request, headers: &str, signed_headers: &str, body:
 fn get_headers(
     headers: &HeaderMap,
     signed_headers: Option<&[HeaderName]>,
-) -> anyhow::Result<(String, String)> {
+) -> std::result::Result<(String, String), TencentCloudError> {
     let mut signed_headers = signed_headers.unwrap_or_default().to_vec();
     if headers.get(ACTION_HEADER).is_some() {
         signed_headers.push(ACTION_HEADER.parse().unwrap());
@@ -162,9 +196,11 @@ fn get_headers(
         .iter()
         .map(|key| match headers.get(key).map(|value| value.to_str()) {
             Some(Ok(value)) => Ok(format!("{key}:{}\n", value.trim().to_lowercase())),
-            _ => Err(anyhow::anyhow!("extracting {key:?} in headers fails")),
+            _ => Err(TencentCloudError::Signature(format!(
+                "extracting {key:?} in headers fails"
+            ))),
         })
-        .collect::<anyhow::Result<Vec<_>, _>>()?
+        .collect::<std::result::Result<Vec<_>, _>>()?
         .join("");
     Ok((headers_string, signed_headers.join(";")))
 }
@@ -176,7 +212,7 @@ mod tests {
     use reqwest::{Client, Method, RequestBuilder, Url};
     use serde_json::json;
 
-    use crate::client::constant::ACTION_HEADER;
+    use crate::client::{constant::ACTION_HEADER, credential::StaticCredentialProvider};
 
     #[allow(unused_imports)]
     use super::*;
@@ -196,9 +232,9 @@ mod tests {
             .header(ACTION_HEADER, "describeinstances");
         let mut req = req_builder.build().unwrap();
 
+        let credential = Credential::new("ak", "sk");
         let signature_context = SignatureContext {
-            ak: "ak".into(),
-            sk: "sk".into(),
+            credential: Arc::new(StaticCredentialProvider::new(credential.clone())),
             signed_headers: Some(vec![
                 HeaderName::from_str("content-type").unwrap(),
                 HeaderName::from_str("host").unwrap(),
@@ -208,8 +244,16 @@ mod tests {
             version: "2017-03-12".into(),
         };
 
-        let r = add_authorization(&mut req, &signature_context).unwrap();
+        let r = add_authorization(&mut req, &credential, &signature_context).unwrap();
 
         println!("r: {r:?}");
+        assert!(req.headers().get(TOKEN_HEADER).is_none());
+
+        let credential = Credential {
+            token: Some("token".into()),
+            ..credential
+        };
+        add_authorization(&mut req, &credential, &signature_context).unwrap();
+        assert_eq!(req.headers().get(TOKEN_HEADER).unwrap(), "token");
     }
 }
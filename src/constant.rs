@@ -99,4 +99,15 @@ pub enum InstanceType {
 
     #[strum(serialize = "SA2.2XLARGE32")]
     SA22Xlarge32, //8C32G
-}
\ No newline at end of file
+}
+
+/// Language of error messages, sent as `X-TC-Language`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display, Serialize)]
+pub enum Language {
+    #[strum(serialize = "zh-CN")]
+    #[serde(rename = "zh-CN")]
+    ZhCN,
+    #[strum(serialize = "en-US")]
+    #[serde(rename = "en-US")]
+    EnUS,
+}
@@ -0,0 +1,88 @@
+use reqwest::StatusCode;
+
+use crate::{client::ApiError, error_code::ErrorCode};
+
+pub type Result<T> = std::result::Result<T, TencentCloudError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TencentCloudError {
+    /// connection, timeout, tls or other failures while sending the request.
+    #[error("transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+
+    /// failure from a `reqwest_middleware` layer that is not ours.
+    #[error("middleware error: {0}")]
+    Middleware(anyhow::Error),
+
+    /// failure while signing the request in `add_authorization`.
+    #[error("signature error: {0}")]
+    Signature(String),
+
+    /// no usable credential from the `CredentialProvider`.
+    #[error("credential error: {0}")]
+    Credential(String),
+
+    /// non 200 http status.
+    #[error("err get code {status}, msg {body}")]
+    Status { status: StatusCode, body: String },
+
+    /// `Response.Error` returned by the api.
+    #[error(transparent)]
+    Api(#[from] ApiError),
+
+    /// the waiter timeout elapsed before the instance reached the expected state.
+    #[error("timed out after {elapsed:?} waiting for instance {instance_id}")]
+    WaitTimeout {
+        instance_id: String,
+        elapsed: std::time::Duration,
+    },
+
+    /// the instance ended in a state it will not leave, e.g. `LAUNCH_FAILED`.
+    #[error("instance {instance_id} failed: {reason}")]
+    InstanceFailed { instance_id: String, reason: String },
+
+    /// the security group rules changed between planning and applying a reconcile.
+    #[error(
+        "security group {security_group_id} changed, planned at version {expected}, now {actual}"
+    )]
+    PolicyVersionConflict {
+        security_group_id: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// response body does not match the expected model.
+    #[error("decode error: {source}, body: {body}")]
+    Decode {
+        #[source]
+        source: serde_json::Error,
+        body: String,
+    },
+}
+
+impl TencentCloudError {
+    /// the api error, if the request reached tencent cloud and was rejected.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            TencentCloudError::Api(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// the parsed api error code, if any.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        self.api_error().map(ApiError::error_code)
+    }
+}
+
+impl From<reqwest_middleware::Error> for TencentCloudError {
+    fn from(e: reqwest_middleware::Error) -> Self {
+        match e {
+            reqwest_middleware::Error::Reqwest(e) => TencentCloudError::Transport(e),
+            reqwest_middleware::Error::Middleware(e) => match e.downcast::<TencentCloudError>() {
+                Ok(e) => e,
+                Err(e) => TencentCloudError::Middleware(e),
+            },
+        }
+    }
+}
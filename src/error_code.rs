@@ -0,0 +1,242 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use strum::{Display, EnumString};
+
+/// Tencent Cloud API 3.0 error code, parsed from `Response.Error.Code`.
+///
+/// Codes look like `Category` or `Category.SubCode`. Categories with a small documented set of
+/// sub codes get their own enum, the others keep the sub code as a string, and anything unknown
+/// lands in [`ErrorCode::Other`].
+///
+/// see <https://cloud.tencent.com/document/api/213/15694>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorCode {
+    ActionOffline,
+    AuthFailure(Option<AuthFailure>),
+    DryRunOperation,
+    FailedOperation(Option<String>),
+    InternalError(Option<String>),
+    InvalidAction,
+    InvalidParameter(Option<String>),
+    InvalidParameterValue(Option<String>),
+    InvalidRequest,
+    IpInBlacklist,
+    IpNotInWhitelist,
+    LimitExceeded(Option<String>),
+    MissingParameter(Option<String>),
+    NoSuchProduct,
+    NoSuchVersion,
+    OperationDenied(Option<String>),
+    RequestLimitExceeded(Option<RequestLimitExceeded>),
+    RequestSizeLimitExceeded,
+    ResourceInUse(Option<String>),
+    ResourceInsufficient(Option<ResourceInsufficient>),
+    ResourceNotFound(Option<String>),
+    ResourceUnavailable(Option<String>),
+    ResourcesSoldOut(Option<String>),
+    ResponseSizeLimitExceeded,
+    ServiceUnavailable,
+    UnauthorizedOperation(Option<String>),
+    UnknownParameter(Option<String>),
+    UnsupportedOperation(Option<String>),
+    UnsupportedProtocol,
+    UnsupportedRegion,
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumString, Display)]
+pub enum AuthFailure {
+    InvalidAuthorization,
+    InvalidSecretId,
+    MFAFailure,
+    SecretIdNotFound,
+    SignatureExpire,
+    SignatureFailure,
+    TokenFailure,
+    UnauthorizedOperation,
+    #[strum(default)]
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumString, Display)]
+pub enum RequestLimitExceeded {
+    GlobalRegionUinLimitExceeded,
+    IPLimitExceeded,
+    UinLimitExceeded,
+    #[strum(default)]
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumString, Display)]
+pub enum ResourceInsufficient {
+    AvailabilityZoneSoldOut,
+    CloudDiskSoldOut,
+    CloudDiskUnavailable,
+    DisasterRecoverGroupCvmQuota,
+    SpecifiedInstanceType,
+    ZoneSoldOutForSpecifiedInstance,
+    #[strum(default)]
+    Other(String),
+}
+
+impl ErrorCode {
+    /// throttling and server side failures that may succeed when sent again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::RequestLimitExceeded(_) | ErrorCode::InternalError(_)
+        )
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (category, sub) = match s.split_once('.') {
+            Some((category, sub)) => (category, Some(sub)),
+            None => (s, None),
+        };
+        let sub_code = || sub.map(str::to_owned);
+        let code = match (category, sub) {
+            ("ActionOffline", None) => ErrorCode::ActionOffline,
+            ("AuthFailure", _) => ErrorCode::AuthFailure(sub.map(|s| s.parse().unwrap())),
+            ("DryRunOperation", None) => ErrorCode::DryRunOperation,
+            ("FailedOperation", _) => ErrorCode::FailedOperation(sub_code()),
+            ("InternalError", _) => ErrorCode::InternalError(sub_code()),
+            ("InvalidAction", None) => ErrorCode::InvalidAction,
+            ("InvalidParameter", _) => ErrorCode::InvalidParameter(sub_code()),
+            ("InvalidParameterValue", _) => ErrorCode::InvalidParameterValue(sub_code()),
+            ("InvalidRequest", None) => ErrorCode::InvalidRequest,
+            ("IpInBlacklist", None) => ErrorCode::IpInBlacklist,
+            ("IpNotInWhitelist", None) => ErrorCode::IpNotInWhitelist,
+            ("LimitExceeded", _) => ErrorCode::LimitExceeded(sub_code()),
+            ("MissingParameter", _) => ErrorCode::MissingParameter(sub_code()),
+            ("NoSuchProduct", None) => ErrorCode::NoSuchProduct,
+            ("NoSuchVersion", None) => ErrorCode::NoSuchVersion,
+            ("OperationDenied", _) => ErrorCode::OperationDenied(sub_code()),
+            ("RequestLimitExceeded", _) => {
+                ErrorCode::RequestLimitExceeded(sub.map(|s| s.parse().unwrap()))
+            }
+            ("RequestSizeLimitExceeded", None) => ErrorCode::RequestSizeLimitExceeded,
+            ("ResourceInUse", _) => ErrorCode::ResourceInUse(sub_code()),
+            ("ResourceInsufficient", _) => {
+                ErrorCode::ResourceInsufficient(sub.map(|s| s.parse().unwrap()))
+            }
+            ("ResourceNotFound", _) => ErrorCode::ResourceNotFound(sub_code()),
+            ("ResourceUnavailable", _) => ErrorCode::ResourceUnavailable(sub_code()),
+            ("ResourcesSoldOut", _) => ErrorCode::ResourcesSoldOut(sub_code()),
+            ("ResponseSizeLimitExceeded", None) => ErrorCode::ResponseSizeLimitExceeded,
+            ("ServiceUnavailable", None) => ErrorCode::ServiceUnavailable,
+            ("UnauthorizedOperation", _) => ErrorCode::UnauthorizedOperation(sub_code()),
+            ("UnknownParameter", _) => ErrorCode::UnknownParameter(sub_code()),
+            ("UnsupportedOperation", _) => ErrorCode::UnsupportedOperation(sub_code()),
+            ("UnsupportedProtocol", None) => ErrorCode::UnsupportedProtocol,
+            ("UnsupportedRegion", None) => ErrorCode::UnsupportedRegion,
+            _ => ErrorCode::Other(s.to_owned()),
+        };
+        Ok(code)
+    }
+}
+
+impl From<&str> for ErrorCode {
+    fn from(s: &str) -> Self {
+        s.parse().unwrap()
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_code<T: fmt::Display>(
+            f: &mut fmt::Formatter<'_>,
+            category: &str,
+            sub: &Option<T>,
+        ) -> fmt::Result {
+            match sub {
+                Some(sub) => write!(f, "{category}.{sub}"),
+                None => f.write_str(category),
+            }
+        }
+        match self {
+            ErrorCode::ActionOffline => f.write_str("ActionOffline"),
+            ErrorCode::AuthFailure(sub) => write_code(f, "AuthFailure", sub),
+            ErrorCode::DryRunOperation => f.write_str("DryRunOperation"),
+            ErrorCode::FailedOperation(sub) => write_code(f, "FailedOperation", sub),
+            ErrorCode::InternalError(sub) => write_code(f, "InternalError", sub),
+            ErrorCode::InvalidAction => f.write_str("InvalidAction"),
+            ErrorCode::InvalidParameter(sub) => write_code(f, "InvalidParameter", sub),
+            ErrorCode::InvalidParameterValue(sub) => write_code(f, "InvalidParameterValue", sub),
+            ErrorCode::InvalidRequest => f.write_str("InvalidRequest"),
+            ErrorCode::IpInBlacklist => f.write_str("IpInBlacklist"),
+            ErrorCode::IpNotInWhitelist => f.write_str("IpNotInWhitelist"),
+            ErrorCode::LimitExceeded(sub) => write_code(f, "LimitExceeded", sub),
+            ErrorCode::MissingParameter(sub) => write_code(f, "MissingParameter", sub),
+            ErrorCode::NoSuchProduct => f.write_str("NoSuchProduct"),
+            ErrorCode::NoSuchVersion => f.write_str("NoSuchVersion"),
+            ErrorCode::OperationDenied(sub) => write_code(f, "OperationDenied", sub),
+            ErrorCode::RequestLimitExceeded(sub) => write_code(f, "RequestLimitExceeded", sub),
+            ErrorCode::RequestSizeLimitExceeded => f.write_str("RequestSizeLimitExceeded"),
+            ErrorCode::ResourceInUse(sub) => write_code(f, "ResourceInUse", sub),
+            ErrorCode::ResourceInsufficient(sub) => write_code(f, "ResourceInsufficient", sub),
+            ErrorCode::ResourceNotFound(sub) => write_code(f, "ResourceNotFound", sub),
+            ErrorCode::ResourceUnavailable(sub) => write_code(f, "ResourceUnavailable", sub),
+            ErrorCode::ResourcesSoldOut(sub) => write_code(f, "ResourcesSoldOut", sub),
+            ErrorCode::ResponseSizeLimitExceeded => f.write_str("ResponseSizeLimitExceeded"),
+            ErrorCode::ServiceUnavailable => f.write_str("ServiceUnavailable"),
+            ErrorCode::UnauthorizedOperation(sub) => write_code(f, "UnauthorizedOperation", sub),
+            ErrorCode::UnknownParameter(sub) => write_code(f, "UnknownParameter", sub),
+            ErrorCode::UnsupportedOperation(sub) => write_code(f, "UnsupportedOperation", sub),
+            ErrorCode::UnsupportedProtocol => f.write_str("UnsupportedProtocol"),
+            ErrorCode::UnsupportedRegion => f.write_str("UnsupportedRegion"),
+            ErrorCode::Other(code) => f.write_str(code),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_code() {
+        assert_eq!(
+            ErrorCode::from("AuthFailure.SignatureExpire"),
+            ErrorCode::AuthFailure(Some(AuthFailure::SignatureExpire))
+        );
+        assert_eq!(
+            ErrorCode::from("ResourceInsufficient.SpecifiedInstanceType"),
+            ErrorCode::ResourceInsufficient(Some(ResourceInsufficient::SpecifiedInstanceType))
+        );
+        assert_eq!(
+            ErrorCode::from("RequestLimitExceeded"),
+            ErrorCode::RequestLimitExceeded(None)
+        );
+        assert_eq!(
+            ErrorCode::from("InvalidParameterValue.InvalidImageId"),
+            ErrorCode::InvalidParameterValue(Some("InvalidImageId".into()))
+        );
+        assert_eq!(
+            ErrorCode::from("AuthFailure.SomethingNew"),
+            ErrorCode::AuthFailure(Some(AuthFailure::Other("SomethingNew".into())))
+        );
+        assert_eq!(
+            ErrorCode::from("VendorSpecific.Code"),
+            ErrorCode::Other("VendorSpecific.Code".into())
+        );
+    }
+
+    #[test]
+    fn test_error_code_round_trip() {
+        for code in [
+            "AuthFailure.SecretIdNotFound",
+            "AuthFailure.Unknown",
+            "InternalError",
+            "LimitExceeded.InstanceQuota",
+            "ResourceInsufficient.ZoneSoldOutForSpecifiedInstance",
+            "ResourceInsufficient.Unknown",
+            "Vendor.Code",
+        ] {
+            assert_eq!(ErrorCode::from(code).to_string(), code);
+        }
+    }
+}
@@ -1,3 +1,5 @@
 pub mod client;
 pub mod config;
-pub mod constant;
\ No newline at end of file
+pub mod constant;
+pub mod error;
+pub mod error_code;
\ No newline at end of file
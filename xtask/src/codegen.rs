@@ -0,0 +1,456 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// `api.json` as published with the official sdks.
+#[derive(Debug, Deserialize)]
+pub struct ApiSpec {
+    pub actions: BTreeMap<String, ActionSpec>,
+    pub metadata: Metadata,
+    pub objects: BTreeMap<String, ObjectSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActionSpec {
+    pub input: String,
+    pub output: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    pub api_version: String,
+    pub service_short_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ObjectSpec {
+    #[serde(default)]
+    pub document: String,
+    #[serde(default)]
+    pub members: Vec<MemberSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemberSpec {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub member: String,
+    #[serde(default)]
+    pub document: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub output_required: bool,
+    #[serde(default)]
+    pub value_allowed_null: bool,
+}
+
+pub fn run(specs: &Path, out: &Path) -> Result<()> {
+    fs::create_dir_all(out)?;
+    for (file, code) in render(specs)? {
+        fs::write(out.join(&file), code)?;
+        println!("generated {}", out.join(&file).display());
+    }
+    Ok(())
+}
+
+/// fail if the files in `out` differ from what [`run`] would write, or are not generated anymore.
+pub fn check(specs: &Path, out: &Path) -> Result<()> {
+    let expected: BTreeMap<_, _> = render(specs)?.into_iter().collect();
+    let mut stale = Vec::new();
+    for (file, code) in &expected {
+        if fs::read_to_string(out.join(file)).ok().as_ref() != Some(code) {
+            stale.push(file.clone());
+        }
+    }
+    if out.exists() {
+        for entry in fs::read_dir(out)? {
+            let file = entry?.file_name().to_string_lossy().into_owned();
+            if file.ends_with(".rs") && !expected.contains_key(&file) {
+                stale.push(file);
+            }
+        }
+    }
+    match stale.is_empty() {
+        true => Ok(()),
+        false => Err(format!(
+            "{} out of date with {}, run `cargo xtask codegen`: {}",
+            out.display(),
+            specs.display(),
+            stale.join(", ")
+        )
+        .into()),
+    }
+}
+
+/// file name and code of every module generated from `specs`, plus `mod.rs`.
+fn render(specs: &Path) -> Result<Vec<(String, String)>> {
+    let mut files = Vec::new();
+    find_specs(specs, &mut files)?;
+    files.sort();
+
+    let mut rendered = Vec::new();
+    let mut modules = Vec::new();
+    for file in files {
+        let spec: ApiSpec = serde_json::from_str(&fs::read_to_string(&file)?)
+            .map_err(|e| format!("{}: {e}", file.display()))?;
+        let module = module_name(&spec.metadata);
+        // `/` separated on every platform, it ends up in the generated code
+        let source = file
+            .strip_prefix(specs)
+            .unwrap_or(&file)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        rendered.push((format!("{module}.rs"), generate_module(&spec, &source)));
+        modules.push((module, spec.metadata));
+    }
+    rendered.push(("mod.rs".to_owned(), generate_mod(&modules)));
+    Ok(rendered)
+}
+
+fn find_specs(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_specs(&path, files)?;
+        } else if path.file_name().is_some_and(|name| name == "api.json") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// `cvm` + `2017-03-12` => `cvm_v20170312`
+fn module_name(metadata: &Metadata) -> String {
+    format!(
+        "{}_v{}",
+        metadata.service_short_name.replace('-', "_"),
+        metadata.api_version.replace('-', "")
+    )
+}
+
+const HEADER: &str = "// @generated by `cargo xtask codegen`, do not edit.";
+
+fn generate_mod(modules: &[(String, Metadata)]) -> String {
+    let mut s = format!("{HEADER}\n");
+    if modules.is_empty() {
+        return s;
+    }
+    s.push('\n');
+    for (module, _) in modules {
+        let _ = writeln!(s, "pub mod {module};");
+    }
+    s.push_str("\nimpl super::TencentCloudClient {\n");
+    for (module, metadata) in modules {
+        let _ = writeln!(
+            s,
+            "    /// {} {}\n    pub fn {module}(&self) -> {module}::{} {{\n        {module}::{}::new(self.client.clone())\n    }}",
+            metadata.service_short_name,
+            metadata.api_version,
+            builder_name(module),
+            builder_name(module),
+        );
+    }
+    s.push_str("}\n");
+    s
+}
+
+/// `cvm_v20170312` => `CvmV20170312Builder`
+fn builder_name(module: &str) -> String {
+    let mut name: String = module.split('_').map(upper_first).collect();
+    name.push_str("Builder");
+    name
+}
+
+fn upper_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+pub fn generate_module(spec: &ApiSpec, source: &str) -> String {
+    let mut s = String::new();
+    let _ = writeln!(s, "{HEADER}");
+    let _ = writeln!(s, "// source: {source}");
+    s.push_str(
+        "\nuse std::sync::Arc;\n\nuse serde::{Deserialize, Serialize};\n\n\
+         use crate::{\n    client::{impl_action, TencentCloudBaseClient},\n    constant::Region,\n};\n\n",
+    );
+    let _ = writeln!(
+        s,
+        "pub const SERVICE: &str = \"{}\";",
+        spec.metadata.service_short_name
+    );
+    let _ = writeln!(
+        s,
+        "pub const VERSION: &str = \"{}\";\n",
+        spec.metadata.api_version
+    );
+
+    for action in spec.actions.keys() {
+        let _ = writeln!(
+            s,
+            "pub const {}: &str = \"{action}\";",
+            snake_case(action).to_ascii_uppercase()
+        );
+    }
+
+    let responses: Vec<_> = spec.actions.values().map(|a| a.output.as_str()).collect();
+    for (name, object) in &spec.objects {
+        s.push('\n');
+        generate_object(&mut s, name, object, responses.contains(&name.as_str()));
+    }
+
+    s.push('\n');
+    for (action, spec) in &spec.actions {
+        let _ = writeln!(
+            s,
+            "impl_action!(SERVICE, VERSION, \"{action}\", {} => {});",
+            spec.input, spec.output
+        );
+    }
+
+    let module = module_name(&spec.metadata);
+    let builder = builder_name(&module);
+    let _ = write!(
+        s,
+        "\npub struct {builder} {{\n    client: Arc<TencentCloudBaseClient>,\n}}\n\n\
+         impl {builder} {{\n    pub fn new(client: Arc<TencentCloudBaseClient>) -> Self {{\n        Self {{ client }}\n    }}\n"
+    );
+    for (action, action_spec) in &spec.actions {
+        s.push('\n');
+        if !action_spec.name.is_empty() {
+            let _ = writeln!(s, "    /// {}", doc_line(&action_spec.name));
+        }
+        if action_spec.status == "deprecated" {
+            s.push_str("    #[deprecated]\n");
+        }
+        let _ = write!(
+            s,
+            "    pub async fn {}(\n        &self,\n        region: &Region,\n        request: &{},\n    ) -> crate::error::Result<{}> {{\n        self.client.execute(region, request).await\n    }}\n",
+            field_name(action),
+            action_spec.input,
+            action_spec.output,
+        );
+    }
+    s.push_str("}\n");
+    s
+}
+
+fn generate_object(s: &mut String, name: &str, object: &ObjectSpec, is_response: bool) {
+    if !object.document.is_empty() {
+        let _ = writeln!(s, "/// {}", doc_line(&object.document));
+    }
+    let _ = write!(
+        s,
+        "#[derive(Debug, Clone, Default, Serialize, Deserialize)]\npub struct {name} {{"
+    );
+    if object.members.is_empty() {
+        s.push_str("}\n");
+        return;
+    }
+    s.push('\n');
+    for member in &object.members {
+        if !member.document.is_empty() {
+            let _ = writeln!(s, "    /// {}", doc_line(&member.document));
+        }
+        let ty = rust_type(member);
+        // request members follow `required`, response members may be missing or null unless
+        // documented otherwise
+        let optional = match is_response {
+            true => !member.output_required || member.value_allowed_null,
+            false => !member.required,
+        };
+        let _ = writeln!(s, "    #[serde(rename = \"{}\")]", member.name);
+        if optional {
+            let _ = writeln!(
+                s,
+                "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub {}: Option<{ty}>,",
+                field_name(&member.name)
+            );
+        } else {
+            let _ = writeln!(
+                s,
+                "    #[serde(default)]\n    pub {}: {ty},",
+                field_name(&member.name)
+            );
+        }
+    }
+    s.push_str("}\n");
+}
+
+fn rust_type(member: &MemberSpec) -> String {
+    let inner = match member.member.as_str() {
+        "string" | "binary" | "date" | "datetime" | "datetime_iso" | "timestamp" => "String",
+        "int" | "int64" | "integer" => "i64",
+        "uint64" | "uint" => "u64",
+        "float" | "double" => "f64",
+        "bool" | "boolean" => "bool",
+        "object" => "serde_json::Value",
+        other => other,
+    };
+    match member.ty.as_str() {
+        "list" => format!("Vec<{inner}>"),
+        _ => inner.to_owned(),
+    }
+}
+
+/// first line of a document, without markup that breaks a `///` comment.
+fn doc_line(document: &str) -> String {
+    document
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .replace('\r', "")
+}
+
+/// `InstanceIdSet` => `instance_id_set`, `CPU` => `cpu`, `IPv6Address` => `ipv6_address`
+fn snake_case(name: &str) -> String {
+    // `IPv4`/`IPv6` are one word, like `Ipv6CidrBlock`
+    let name = name.replace("IPv4", "Ipv4").replace("IPv6", "Ipv6");
+    let chars: Vec<char> = name.chars().collect();
+    let mut s = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                s.push('_');
+            }
+        }
+        if c == '-' || c == '.' {
+            s.push('_');
+        } else {
+            s.push(c.to_ascii_lowercase());
+        }
+    }
+    s
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+fn field_name(name: &str) -> String {
+    let name = snake_case(name);
+    match name.as_str() {
+        "self" | "super" | "crate" => format!("{name}_"),
+        n if KEYWORDS.contains(&n) => format!("r#{name}"),
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"{
+        "actions": {
+            "DescribeZones": {
+                "document": "本接口(DescribeZones)用于查询可用区信息。",
+                "input": "DescribeZonesRequest",
+                "name": "查询可用区列表",
+                "output": "DescribeZonesResponse",
+                "status": "online"
+            }
+        },
+        "metadata": {
+            "apiVersion": "2017-03-12",
+            "serviceNameCN": "云服务器",
+            "serviceShortName": "cvm"
+        },
+        "objects": {
+            "DescribeZonesRequest": {
+                "document": "DescribeZones请求参数结构体",
+                "members": [],
+                "type": "object"
+            },
+            "DescribeZonesResponse": {
+                "document": "DescribeZones返回参数结构体",
+                "members": [
+                    {"document": "可用区数量。", "member": "uint64", "name": "TotalCount", "output_required": true, "type": "int"},
+                    {"document": "可用区列表信息。", "member": "ZoneInfo", "name": "ZoneSet", "output_required": true, "type": "list"},
+                    {"document": "唯一请求 ID", "member": "string", "name": "RequestId", "type": "string"}
+                ],
+                "type": "object"
+            },
+            "ZoneInfo": {
+                "document": "可用区信息",
+                "members": [
+                    {"document": "可用区名称", "member": "string", "name": "Zone", "output_required": true, "type": "string"},
+                    {"document": "类型", "member": "string", "name": "Type", "type": "string"}
+                ],
+                "type": "object",
+                "usage": "out"
+            }
+        },
+        "version": "1.0"
+    }"#;
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("InstanceIdSet"), "instance_id_set");
+        assert_eq!(snake_case("CPU"), "cpu");
+        assert_eq!(snake_case("IPv6Address"), "ipv6_address");
+        assert_eq!(snake_case("PrivateIPv6Addresses"), "private_ipv6_addresses");
+        assert_eq!(snake_case("Ipv6CidrBlock"), "ipv6_cidr_block");
+        assert_eq!(field_name("Type"), "r#type");
+    }
+
+    #[test]
+    fn test_generate_module() {
+        let spec: ApiSpec = serde_json::from_str(SPEC).unwrap();
+        let code = generate_module(&spec, "cvm/v20170312/api.json");
+        for expected in [
+            "pub const SERVICE: &str = \"cvm\";",
+            "pub const VERSION: &str = \"2017-03-12\";",
+            "pub const DESCRIBE_ZONES: &str = \"DescribeZones\";",
+            "pub struct DescribeZonesRequest {}",
+            "    #[serde(rename = \"ZoneSet\")]\n    #[serde(default)]\n    pub zone_set: Vec<ZoneInfo>,",
+            "    pub total_count: u64,",
+            "    pub request_id: Option<String>,",
+            "    pub r#type: Option<String>,",
+            "impl_action!(SERVICE, VERSION, \"DescribeZones\", DescribeZonesRequest => DescribeZonesResponse);",
+            "pub struct CvmV20170312Builder {",
+            "    pub async fn describe_zones(",
+        ] {
+            assert!(code.contains(expected), "missing {expected:?} in\n{code}");
+        }
+    }
+
+    /// the checked in modules must be what `cargo xtask codegen` makes of the checked in specs.
+    #[test]
+    fn test_generated_up_to_date() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        check(&root.join("specs"), &root.join("src/client/generated")).unwrap();
+    }
+}
@@ -0,0 +1,55 @@
+use std::{path::PathBuf, process::ExitCode};
+
+mod codegen;
+
+const USAGE: &str = "usage: cargo xtask codegen [--specs <dir>] [--out <dir>] [--check]
+
+reads every `<specs>/<service>/<version>/api.json` (the api spec files published with the
+official tencentcloud sdks, e.g. `cvm/v20170312/api.json`) and writes one module per service and
+version into `<out>`, default `specs` and `src/client/generated`.
+
+with `--check` nothing is written, it fails if `<out>` is not up to date with `<specs>`.";
+
+fn main() -> ExitCode {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives in the workspace root")
+        .to_path_buf();
+    let mut args = std::env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("codegen") => {
+            let mut specs = root.join("specs");
+            let mut out = root.join("src/client/generated");
+            let mut check = false;
+            while let Some(arg) = args.next() {
+                if arg == "--check" {
+                    check = true;
+                    continue;
+                }
+                match (arg.as_str(), args.next()) {
+                    ("--specs", Some(dir)) => specs = dir.into(),
+                    ("--out", Some(dir)) => out = dir.into(),
+                    _ => {
+                        eprintln!("{USAGE}");
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+            match check {
+                true => codegen::check(&specs, &out),
+                false => codegen::run(&specs, &out),
+            }
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}